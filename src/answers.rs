@@ -0,0 +1,185 @@
+//! Recorded answers, used to check that every day still produces the same results.
+//!
+//! The answers are kept in a TOML file with a table per day:
+//!
+//! ```toml
+//! [day07]
+//! part1 = 3749
+//! part2 = 11387
+//! ```
+//!
+//! Text answers, and integers too large for TOML, are stored as strings.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use toml::{Table, Value};
+
+use crate::Answer;
+
+/// Name of the answers file in the project root.
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// The answers file used when none is given on the command line.
+pub fn default_answers_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_ANSWERS_FILE)
+}
+
+/// The outcome of comparing an answer with the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch {
+        expected: String,
+    },
+    /// Nothing has been recorded for this part.
+    Missing,
+    /// The part is unsolved and nothing has been recorded for it.
+    Unsolved,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. })
+    }
+}
+
+/// The recorded answers, keyed by day and part.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    table: Table,
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: usize) -> String {
+    format!("part{}", part)
+}
+
+/// A recorded answer as it would be displayed.
+pub(crate) fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            table: text.parse::<Table>()?,
+        })
+    }
+
+    /// Reads the answers file.  A file that does not exist yet holds no answers.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("Reading {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("Writing {}", path.display()))
+    }
+
+    /// The recorded answer for the part, as it would be displayed.
+    pub fn get(&self, day: u8, part: usize) -> Option<String> {
+        let value = self.table.get(&day_key(day))?.get(part_key(part))?;
+        Some(display_value(value))
+    }
+
+    /// Records the answer unless one is already stored or the part is unsolved.
+    /// Returns whether the answer was added.
+    pub fn insert(&mut self, day: u8, part: usize, answer: &Answer) -> bool {
+        let value = match answer {
+            Answer::Int(value) => Value::Integer(*value),
+            Answer::UInt(value) => match i64::try_from(*value) {
+                Ok(value) => Value::Integer(value),
+                Err(_) => Value::String(value.to_string()),
+            },
+            Answer::Text(text) => Value::String(text.clone()),
+            Answer::Unsolved => return false,
+        };
+        let Value::Table(parts) = self
+            .table
+            .entry(day_key(day))
+            .or_insert_with(|| Value::Table(Table::new()))
+        else {
+            return false;
+        };
+        if parts.contains_key(&part_key(part)) {
+            return false;
+        }
+        parts.insert(part_key(part), value);
+        true
+    }
+
+    /// Compares the answer with the recorded one by their displayed forms.
+    pub fn check(&self, day: u8, part: usize, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if *answer != Answer::Unsolved && expected == answer.to_string() => {
+                Verdict::Match
+            }
+            Some(expected) => Verdict::Mismatch { expected },
+            None if *answer == Answer::Unsolved => Verdict::Unsolved,
+            None => Verdict::Missing,
+        }
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_integers_and_strings() {
+        let answers = Answers::parse("[day07]\npart1 = 3749\npart2 = \"abc\"\n").unwrap();
+        assert_eq!(answers.get(7, 1), Some("3749".to_string()));
+        assert_eq!(answers.get(7, 2), Some("abc".to_string()));
+        assert_eq!(answers.get(8, 1), None);
+    }
+
+    #[test]
+    fn insert_keeps_existing_answers() {
+        let mut answers = Answers::default();
+        assert!(answers.insert(3, 1, &Answer::Int(-4)));
+        assert!(!answers.insert(3, 1, &Answer::Int(5)));
+        assert!(!answers.insert(3, 2, &Answer::Unsolved));
+        assert_eq!(answers.get(3, 1), Some("-4".to_string()));
+        assert_eq!(answers.get(3, 2), None);
+    }
+
+    #[test]
+    fn large_unsigned_answers_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(9, 2, &Answer::UInt(u64::MAX));
+        let reread = Answers::parse(&answers.to_string()).unwrap();
+        assert_eq!(reread.check(9, 2, &Answer::UInt(u64::MAX)), Verdict::Match);
+    }
+
+    #[test]
+    fn check_compares_displayed_answers() {
+        let answers = Answers::parse("[day01]\npart1 = 11\n").unwrap();
+        assert_eq!(answers.check(1, 1, &Answer::UInt(11)), Verdict::Match);
+        assert_eq!(
+            answers.check(1, 1, &Answer::UInt(12)),
+            Verdict::Mismatch {
+                expected: "11".to_string()
+            }
+        );
+        assert_eq!(answers.check(1, 2, &Answer::UInt(1)), Verdict::Missing);
+        assert_eq!(answers.check(1, 2, &Answer::Unsolved), Verdict::Unsolved);
+        assert!(answers.check(1, 1, &Answer::Unsolved).is_failure());
+    }
+}
@@ -0,0 +1,266 @@
+//! Running days over every input in a directory, such as inputs collected from several
+//! people or written by `aoc generate`, to check that the solutions generalise.
+//!
+//! A file is for the day its name starts with, like `07.txt` or `09-42.txt`.  When a
+//! single day is run, files whose names do not start with a day are also given to it, so
+//! a directory of inputs for one day can be named after whoever they came from.
+//!
+//! The files are run in parallel when the `parallel` feature is on.  A file that fails
+//! to parse, or whose solution panics, is reported and the rest carry on.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::par::prelude::*;
+use crate::runner::{self, format_columns, Align, DayResult, InputSource, Runnable};
+use crate::Overrides;
+
+/// An input file and the day to run on it.
+#[derive(Clone, Copy)]
+pub struct BatchJob<'a> {
+    pub path: &'a Path,
+    pub day: &'static dyn Runnable,
+}
+
+/// How running a day on one input file went.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchResult {
+    pub path: PathBuf,
+    pub day: u8,
+    /// The answers, or why there are none.
+    pub outcome: Result<DayResult, String>,
+    /// The time taken to read the file, parse it and solve the parts.
+    pub elapsed: Duration,
+}
+
+/// The day a file is for, from the two digits its name starts with.
+pub fn day_of_file(path: &Path) -> Option<u8> {
+    let name = path.file_name()?.to_str()?;
+    let n_digits = name.bytes().take_while(u8::is_ascii_digit).count();
+    if n_digits != 2 {
+        return None;
+    }
+    name[..2].parse().ok()
+}
+
+/// The files in the directory, other than hidden ones, in order of name.
+pub fn input_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Reading {}", dir.display()))? {
+        let entry = entry?;
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if entry.file_type()?.is_file() && !hidden {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Pairs each file with the day it is for, among the days given.  Files for other days
+/// are left out, as are files without a day unless exactly one day is given.
+pub fn jobs<'a>(files: &'a [PathBuf], days: &[&'static dyn Runnable]) -> Vec<BatchJob<'a>> {
+    files
+        .iter()
+        .filter_map(|path| {
+            let day = match day_of_file(path) {
+                Some(number) => days.iter().find(|day| day.day() == number)?,
+                None if days.len() == 1 => &days[0],
+                None => return None,
+            };
+            Some(BatchJob { path, day: *day })
+        })
+        .collect()
+}
+
+/// Runs the jobs, in parallel if possible, giving the results in the order of the jobs.
+///
+/// Panics are caught and reported as failures.  The panic hook still prints their
+/// messages as they happen, so callers that want only the table should replace it.
+pub fn run_batch(
+    jobs: &[BatchJob<'_>],
+    part: Option<usize>,
+    overrides: &Overrides,
+) -> Vec<BatchResult> {
+    jobs.par_iter()
+        .map(|job| run_job(job, part, overrides))
+        .collect()
+}
+
+fn run_job(job: &BatchJob<'_>, part: Option<usize>, overrides: &Overrides) -> BatchResult {
+    let start = Instant::now();
+    let outcome = std::fs::read_to_string(job.path)
+        .map_err(|err| format!("Reading {}: {}", job.path.display(), err))
+        .and_then(|input| {
+            let input = crate::normalize_input(&input);
+            let mut result = run_input(job.day, &input, part, overrides)?;
+            result.input = Some(InputSource::new(job.path.to_path_buf(), &input));
+            Ok(result)
+        });
+    BatchResult {
+        path: job.path.to_path_buf(),
+        day: job.day.day(),
+        outcome,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Runs the day on the input, turning an error or a panic into a message.
+pub fn run_input(
+    day: &dyn Runnable,
+    input: &str,
+    part: Option<usize>,
+    overrides: &Overrides,
+) -> Result<DayResult, String> {
+    runner::catch_panic(|| day.run(input, part, overrides)).map_err(|err| format!("{:#}", err))
+}
+
+/// Formats the results as a table with a row per file, giving the answer to each part,
+/// the time taken, and whether the file failed.
+pub fn format_batch(results: &[BatchResult]) -> String {
+    let rows = results
+        .iter()
+        .map(|result| {
+            let answer = |part| match &result.outcome {
+                Ok(day) => day
+                    .parts
+                    .iter()
+                    .find(|p| p.part == part)
+                    .map_or(String::new(), |p| p.answer.to_string()),
+                Err(_) => String::new(),
+            };
+            let status = match &result.outcome {
+                Ok(_) => "ok".to_string(),
+                Err(message) => message.lines().next().unwrap_or("").to_string(),
+            };
+            [
+                result
+                    .path
+                    .file_name()
+                    .map_or(String::new(), |name| name.to_string_lossy().into_owned()),
+                runner::input_name(result.day),
+                answer(1),
+                answer(2),
+                format!("{:.2?}", result.elapsed),
+                status,
+            ]
+        })
+        .collect::<Vec<_>>();
+    format_columns(
+        ["File", "Day", "Part 1", "Part 2", "Elapsed", "Status"],
+        [
+            Align::Left,
+            Align::Right,
+            Align::Left,
+            Align::Left,
+            Align::Right,
+            Align::Left,
+        ],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{find_day, Registered};
+    use crate::{Answer, Params, Solution};
+
+    // Panics on negative numbers.
+    struct PanickyDay;
+
+    impl Solution for PanickyDay {
+        const DAY: u8 = 98;
+        const TITLE: &'static str = "Panicky";
+        type Input = Vec<u32>;
+
+        fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+            Ok(input
+                .trim()
+                .split(',')
+                .map(|n| n.parse().unwrap())
+                .collect())
+        }
+
+        fn part1(input: &Self::Input, _params: &Params) -> Answer {
+            input.iter().sum::<u32>().into()
+        }
+
+        fn part2(_input: &Self::Input, _params: &Params) -> Answer {
+            Answer::Unsolved
+        }
+    }
+
+    static PANICKY_DAY: Registered<PanickyDay> = Registered::new();
+
+    #[test]
+    fn days_come_from_file_names() {
+        assert_eq!(day_of_file(Path::new("dir/07.txt")), Some(7));
+        assert_eq!(day_of_file(Path::new("09-42.txt")), Some(9));
+        assert_eq!(day_of_file(Path::new("14")), Some(14));
+        assert_eq!(day_of_file(Path::new("123.txt")), None);
+        assert_eq!(day_of_file(Path::new("alice.txt")), None);
+        assert_eq!(day_of_file(Path::new("7.txt")), None);
+    }
+
+    #[test]
+    fn files_without_a_day_need_a_single_day() {
+        let files = ["02-1.txt", "07.txt", "alice.txt"].map(PathBuf::from);
+        let day = |n| find_day(n).unwrap();
+        fn names<'a>(jobs: &[BatchJob<'a>]) -> Vec<(&'a str, u8)> {
+            jobs.iter()
+                .map(|job| (job.path.to_str().unwrap(), job.day.day()))
+                .collect()
+        }
+        assert_eq!(
+            names(&jobs(&files, &[day(7)])),
+            vec![("07.txt", 7), ("alice.txt", 7)]
+        );
+        assert_eq!(
+            names(&jobs(&files, &[day(2), day(7)])),
+            vec![("02-1.txt", 2), ("07.txt", 7)]
+        );
+    }
+
+    #[test]
+    fn failures_and_panics_are_reported() {
+        let overrides = Overrides::default();
+        let result = run_input(&PANICKY_DAY, "1,2", None, &overrides).unwrap();
+        assert_eq!(result.parts[0].answer, Answer::from(3_u32));
+
+        let err = run_input(&PANICKY_DAY, "1,-2", None, &overrides).unwrap_err();
+        assert!(err.starts_with("panicked: "), "{}", err);
+
+        let err = run_input(&PANICKY_DAY, "1", Some(3), &overrides).unwrap_err();
+        assert_eq!(err, "Day 98 has no part 3");
+    }
+
+    #[test]
+    fn table_has_a_row_per_file() {
+        let overrides = Overrides::default();
+        let results = [
+            BatchResult {
+                path: PathBuf::from("dir/98-1.txt"),
+                day: 98,
+                outcome: run_input(&PANICKY_DAY, "1,2", Some(1), &overrides),
+                elapsed: Duration::from_millis(3),
+            },
+            BatchResult {
+                path: PathBuf::from("dir/98-2.txt"),
+                day: 98,
+                outcome: Err("line 1, column 1: expected an integer\nmore".to_string()),
+                elapsed: Duration::from_millis(1),
+            },
+        ];
+        let table = format_batch(&results);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            vec!["98-1.txt", "98", "3", "3.00ms", "ok"]
+        );
+        assert!(lines[2].ends_with("line 1, column 1: expected an integer"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Repeated timing of the days, with a baseline file to catch regressions.
+//!
+//! Each run parses the input and solves both parts, and the parse and each part are
+//! timed separately.  The baseline stores the median of each in nanoseconds:
+//!
+//! ```toml
+//! [day07]
+//! parse = 41250
+//! part1 = 1830417
+//! part2 = 95204583
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Context;
+use toml::{Table, Value};
+
+use crate::runner::Runnable;
+use crate::Overrides;
+
+/// Name of the baseline file in the project root.
+pub const DEFAULT_BASELINE_FILE: &str = "bench.toml";
+
+/// The baseline file used when none is given on the command line.
+pub fn default_baseline_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_BASELINE_FILE)
+}
+
+/// A timed step of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Part(usize),
+}
+
+impl Stage {
+    /// The key used for the stage in the baseline file.
+    pub fn key(&self) -> String {
+        match self {
+            Stage::Parse => "parse".to_string(),
+            Stage::Part(part) => format!("part{}", part),
+        }
+    }
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples, or returns None if there are none.  The median of an even
+    /// number of samples is the mean of the middle two.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (*sorted.get(mid.checked_sub(1)?)? + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+        Some(Self {
+            min: *sorted.first()?,
+            median,
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// The timings of one stage of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchResult {
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+/// Runs the day `runs` times on the input, after one untimed warm-up run.
+pub fn bench_day(
+    day: &dyn Runnable,
+    input: &str,
+    runs: usize,
+    overrides: &Overrides,
+) -> anyhow::Result<Vec<BenchResult>> {
+    anyhow::ensure!(runs > 0, "Need at least one run");
+    day.run(input, None, overrides)?;
+
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut part_samples = Vec::<(usize, Vec<Duration>)>::new();
+    for _ in 0..runs {
+        let result = day.run(input, None, overrides)?;
+        parse_samples.push(result.parse_elapsed);
+        for part in result.parts {
+            match part_samples.iter_mut().find(|(p, _)| *p == part.part) {
+                Some((_, samples)) => samples.push(part.elapsed),
+                None => part_samples.push((part.part, vec![part.elapsed])),
+            }
+        }
+    }
+
+    let stages = std::iter::once((Stage::Parse, parse_samples)).chain(
+        part_samples
+            .into_iter()
+            .map(|(part, samples)| (Stage::Part(part), samples)),
+    );
+    Ok(stages
+        .filter_map(|(stage, samples)| {
+            Some(BenchResult {
+                day: day.day(),
+                stage,
+                stats: Stats::from_samples(&samples)?,
+            })
+        })
+        .collect())
+}
+
+/// The recorded median timings, keyed by day and stage.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    table: Table,
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+impl Baseline {
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            table: text.parse::<Table>()?,
+        })
+    }
+
+    /// Reads the baseline file.  A file that does not exist yet holds no timings.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("Reading {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("Writing {}", path.display()))
+    }
+
+    /// The recorded median for the stage.
+    pub fn get(&self, day: u8, stage: Stage) -> Option<Duration> {
+        let nanos = self
+            .table
+            .get(&day_key(day))?
+            .get(stage.key())?
+            .as_integer()?;
+        Some(Duration::from_nanos(u64::try_from(nanos).ok()?))
+    }
+
+    /// Records the result's median, replacing any earlier one.
+    pub fn insert(&mut self, result: &BenchResult) {
+        let nanos = i64::try_from(result.stats.median.as_nanos()).unwrap_or(i64::MAX);
+        let entry = self
+            .table
+            .entry(day_key(result.day))
+            .or_insert_with(|| Value::Table(Table::new()));
+        if let Value::Table(stages) = entry {
+            stages.insert(result.stage.key(), Value::Integer(nanos));
+        }
+    }
+
+    /// The change in the result's median relative to the baseline, as a fraction.
+    pub fn change(&self, result: &BenchResult) -> Option<f64> {
+        let baseline = self.get(result.day, result.stage)?.as_secs_f64();
+        (baseline > 0.0).then(|| result.stats.median.as_secs_f64() / baseline - 1.0)
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn stats_of_odd_number_of_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 9, 3, 4])).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.max, Duration::from_millis(9));
+    }
+
+    #[test]
+    fn median_of_even_number_of_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 1, 2, 8])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn no_samples_has_no_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn baseline_round_trips_and_reports_change() {
+        let stats = Stats::from_samples(&millis(&[2])).unwrap();
+        let result = BenchResult {
+            day: 7,
+            stage: Stage::Part(2),
+            stats,
+        };
+        let mut baseline = Baseline::default();
+        baseline.insert(&result);
+        let baseline = Baseline::parse(&baseline.to_string()).unwrap();
+        assert_eq!(
+            baseline.get(7, Stage::Part(2)),
+            Some(Duration::from_millis(2))
+        );
+        assert_eq!(baseline.get(7, Stage::Parse), None);
+
+        let slower = BenchResult {
+            stats: Stats::from_samples(&millis(&[3])).unwrap(),
+            ..result
+        };
+        assert!((baseline.change(&slower).unwrap() - 0.5).abs() < 1e-9);
+    }
+}
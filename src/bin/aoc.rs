@@ -0,0 +1,674 @@
+// Runs any of the registered days.
+//
+// Usage:
+//   aoc run <day|all> [--part <n>] [--format <table|json>] [--input <file>] [--input-dir <dir>]
+//   aoc verify [day|all] [--answers <file>] [--input <file>] [--input-dir <dir>]
+//   aoc record [day|all] [--answers <file>] [--input <file>] [--input-dir <dir>]
+//   aoc bench [day|all] [--runs <n>] [--baseline <file>] [--threshold <percent>] [--save]
+//   aoc crosscheck [day|all] [--input <file>] [--input-dir <dir>]
+//   aoc batch <day|all> <dir> [--part <n>]
+//   aoc generate <day|all> [--seed <n>] [--count <n>] [--out-dir <dir>]
+//   aoc render <6|14|15> --out <file> [--scale <n>] [--every <n>] [--frames <n>] [--delay <n>]
+//   aoc play <6|14|15> [--speed <steps per second>]
+//   aoc list
+//
+// Every command that runs days also accepts any number of `--param <name>=<value>` to
+// override the days' parameters, which `aoc list` shows.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use rust_advent::answers::{self, Answers, Verdict};
+use rust_advent::batch;
+use rust_advent::bench::{self, Baseline};
+use rust_advent::generate::{self, Generator};
+use rust_advent::render::{Animation, Image, Palette};
+use rust_advent::runner::{self, Align, DayResult, OutputFormat, Runnable};
+use rust_advent::simulation;
+use rust_advent::{day06, day14, day15, Grid, InputOptions, Overrides, Params, Solution};
+
+const USAGE: &str = "Usage:
+  aoc run <day|all> [--part <n>] [--format <table|json>] [--input <file>] [--input-dir <dir>]
+  aoc verify [day|all] [--answers <file>] [--input <file>] [--input-dir <dir>]
+  aoc record [day|all] [--answers <file>] [--input <file>] [--input-dir <dir>]
+  aoc bench [day|all] [--runs <n>] [--baseline <file>] [--threshold <percent>] [--save]
+  aoc crosscheck [day|all] [--input <file>] [--input-dir <dir>]
+  aoc batch <day|all> <dir> [--part <n>]
+  aoc generate <day|all> [--seed <n>] [--count <n>] [--out-dir <dir>]
+  aoc render <6|14|15> --out <file> [--scale <n>] [--every <n>] [--frames <n>] [--delay <n>]
+  aoc play <6|14|15> [--speed <steps per second>]
+  aoc list
+
+Days with parameters accept --param <name>=<value>, which may be repeated.  So does
+generate, for the sizes of the inputs.  render writes a still image to a .png or .ppm
+file, and an animation to a .gif file.  play steps through a day in the terminal, taking
+a command per line: Enter to step, p to play or pause, g <n> to jump to step n, + or -
+to change speed, and q to quit.";
+
+const DEFAULT_BENCH_RUNS: usize = 10;
+const DEFAULT_REGRESSION_PERCENT: f64 = 10.0;
+const DEFAULT_GENERATED_DIR: &str = "generated";
+const SIMULATED_DAYS: [u8; 3] = [6, 14, 15];
+const DEFAULT_RENDER_SCALE: usize = 4;
+const DEFAULT_RENDER_FRAMES: usize = 100;
+const DEFAULT_FRAME_DELAY: u16 = 5;
+
+fn main() -> anyhow::Result<()> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    rust_advent::set_input_options(InputOptions::from_args(&args).map_err(|msg| anyhow!(msg))?);
+
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("record") => record(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("crosscheck") => cross_check(&args[1..]),
+        Some("batch") => batch(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("render") => render(&args[1..]),
+        Some("play") => play(&args[1..]),
+        Some("list") => {
+            for day in runner::days() {
+                println!("{:>2}  {}", day.day(), day.title());
+                for param in day.params() {
+                    println!(
+                        "      --param {}={}  {} ({} to {})",
+                        param.name, param.default, param.description, param.min, param.max
+                    );
+                }
+                if let Some(generator) = generate::find_generator(day.day()) {
+                    println!("      generate:");
+                    for param in generator.params {
+                        println!(
+                            "        --param {}={}  {} ({} to {})",
+                            param.name, param.default, param.description, param.min, param.max
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => bail!(USAGE),
+    }
+}
+
+// Selects the days to run from the first argument: a day number or "all".
+fn select_days(selection: &str) -> anyhow::Result<Vec<&'static dyn Runnable>> {
+    if selection == "all" {
+        return Ok(runner::days().to_vec());
+    }
+    let day = selection
+        .parse::<u8>()
+        .with_context(|| format!("Invalid day: {}", selection))?;
+    Ok(vec![
+        runner::find_day(day).ok_or_else(|| anyhow!("Day {} is not registered", day))?
+    ])
+}
+
+// The value of `--flag <value>` or `--flag=value`, if given.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> anyhow::Result<Option<&'a str>> {
+    for (idx, arg) in args.iter().enumerate() {
+        if arg == flag {
+            return match args.get(idx + 1) {
+                Some(value) => Ok(Some(value)),
+                None => bail!("{} requires a value", flag),
+            };
+        }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|v| v.strip_prefix('=')) {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+// The days named by an optional leading argument, defaulting to all of them.
+fn optional_days(args: &[String]) -> anyhow::Result<Vec<&'static dyn Runnable>> {
+    match args.first().filter(|arg| !arg.starts_with("--")) {
+        Some(selection) => select_days(selection),
+        None => Ok(runner::days().to_vec()),
+    }
+}
+
+// `--input` is the input for a single day, so it cannot be given to several days.
+fn check_input_file(args: &[String], days: &[&'static dyn Runnable]) -> anyhow::Result<()> {
+    let options = InputOptions::from_args(args).map_err(|msg| anyhow!(msg))?;
+    if options.file.is_some() && days.len() > 1 {
+        bail!("--input can only be used with a single day");
+    }
+    Ok(())
+}
+
+// The `--param` overrides, which must each be declared by at least one of the days.
+fn overrides(args: &[String], days: &[&'static dyn Runnable]) -> anyhow::Result<Overrides> {
+    let overrides = Overrides::from_args(args)?;
+    overrides.check_declared(days.iter().map(|day| day.params()))?;
+    Ok(overrides)
+}
+
+fn answers_path(args: &[String]) -> anyhow::Result<PathBuf> {
+    Ok(flag_value(args, "--answers")?
+        .map(PathBuf::from)
+        .unwrap_or_else(answers::default_answers_path))
+}
+
+// Runs the days, keeping going if one fails or panics so that everything that can be is
+// reported.
+// Returns the results and the number of days that failed.
+fn run_days(
+    days: Vec<&'static dyn Runnable>,
+    part: Option<usize>,
+    overrides: &Overrides,
+) -> (Vec<DayResult>, usize) {
+    let mut results = Vec::new();
+    let mut n_failures = 0;
+    for day in days {
+        match runner::run_day(day, part, overrides) {
+            Ok(result) => results.push(result),
+            Err(err) => {
+                eprintln!("Day {} failed: {:#}", day.day(), err);
+                n_failures += 1;
+            }
+        }
+    }
+    (results, n_failures)
+}
+
+fn run(args: &[String]) -> anyhow::Result<()> {
+    let days = select_days(args.first().ok_or_else(|| anyhow!(USAGE))?)?;
+    check_input_file(args, &days)?;
+    let part = flag_value(args, "--part")?
+        .map(|part| part.parse::<usize>().context("Invalid part"))
+        .transpose()?;
+    let overrides = overrides(args, &days)?;
+    let format = OutputFormat::from_args(args)?;
+
+    let (results, n_failures) = run_days(days, part, &overrides);
+    if !results.is_empty() {
+        print!("{}", format.format(&results));
+    }
+
+    if n_failures > 0 {
+        bail!("{} day(s) failed", n_failures);
+    }
+    Ok(())
+}
+
+fn verify(args: &[String]) -> anyhow::Result<()> {
+    let days = optional_days(args)?;
+    check_input_file(args, &days)?;
+    let path = answers_path(args)?;
+    let answers = Answers::load(&path)?;
+    let overrides = overrides(args, &days)?;
+
+    let (results, n_failures) = run_days(days, None, &overrides);
+    let mut n_mismatches = 0;
+    let mut rows = Vec::new();
+    for result in &results {
+        for part in &result.parts {
+            let verdict = answers.check(result.day, part.part, &part.answer);
+            if verdict.is_failure() {
+                n_mismatches += 1;
+            }
+            let (expected, status) = match verdict {
+                Verdict::Match => (part.answer.to_string(), "ok"),
+                Verdict::Mismatch { expected } => (expected, "MISMATCH"),
+                Verdict::Missing => (String::new(), "missing"),
+                Verdict::Unsolved => (String::new(), "unsolved"),
+            };
+            rows.push([
+                result.day.to_string(),
+                part.part.to_string(),
+                expected,
+                part.answer.to_string(),
+                status.to_string(),
+            ]);
+        }
+    }
+    if !rows.is_empty() {
+        print!(
+            "{}",
+            runner::format_columns(
+                ["Day", "Part", "Expected", "Actual", "Status"],
+                [
+                    Align::Right,
+                    Align::Right,
+                    Align::Left,
+                    Align::Left,
+                    Align::Left
+                ],
+                &rows,
+            )
+        );
+    }
+
+    if n_mismatches > 0 || n_failures > 0 {
+        bail!(
+            "{} mismatched answer(s) and {} failed day(s) against {}",
+            n_mismatches,
+            n_failures,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn record(args: &[String]) -> anyhow::Result<()> {
+    let days = optional_days(args)?;
+    check_input_file(args, &days)?;
+    let path = answers_path(args)?;
+    let mut answers = Answers::load(&path)?;
+    let overrides = overrides(args, &days)?;
+
+    let (results, n_failures) = run_days(days, None, &overrides);
+    let mut n_added = 0;
+    for result in &results {
+        for part in &result.parts {
+            if answers.insert(result.day, part.part, &part.answer) {
+                println!("Day {} part {}: {}", result.day, part.part, part.answer);
+                n_added += 1;
+            }
+        }
+    }
+    if n_added > 0 {
+        answers.save(&path)?;
+    }
+    println!("Recorded {} new answer(s) in {}", n_added, path.display());
+
+    if n_failures > 0 {
+        bail!("{} day(s) failed", n_failures);
+    }
+    Ok(())
+}
+
+fn bench(args: &[String]) -> anyhow::Result<()> {
+    let days = optional_days(args)?;
+    check_input_file(args, &days)?;
+    let runs = flag_value(args, "--runs")?
+        .map(|runs| runs.parse::<usize>().context("Invalid number of runs"))
+        .transpose()?
+        .unwrap_or(DEFAULT_BENCH_RUNS);
+    let threshold = flag_value(args, "--threshold")?
+        .map(|pct| pct.parse::<f64>().context("Invalid threshold"))
+        .transpose()?
+        .unwrap_or(DEFAULT_REGRESSION_PERCENT)
+        / 100.0;
+    let save = args.iter().any(|arg| arg == "--save");
+    let path = flag_value(args, "--baseline")?
+        .map(PathBuf::from)
+        .unwrap_or_else(bench::default_baseline_path);
+    let mut baseline = Baseline::load(&path)?;
+    let overrides = overrides(args, &days)?;
+
+    let mut results = Vec::new();
+    let mut n_failures = 0;
+    for day in days {
+        let bench_result = rust_advent::read_file_as_string(&runner::input_name(day.day()))
+            .map_err(anyhow::Error::from)
+            .and_then(|input| {
+                runner::catch_panic(|| bench::bench_day(day, &input, runs, &overrides))
+            });
+        match bench_result {
+            Ok(day_results) => results.extend(day_results),
+            Err(err) => {
+                eprintln!("Day {} failed: {:#}", day.day(), err);
+                n_failures += 1;
+            }
+        }
+    }
+
+    let mut n_regressions = 0;
+    let mut rows = Vec::new();
+    for result in &results {
+        let change = baseline.change(result);
+        let regressed = change.is_some_and(|change| change > threshold);
+        if regressed {
+            n_regressions += 1;
+        }
+        rows.push([
+            result.day.to_string(),
+            result.stage.to_string(),
+            format!("{:.2?}", result.stats.min),
+            format!("{:.2?}", result.stats.median),
+            format!("{:.2?}", result.stats.max),
+            change.map_or(String::new(), |change| format!("{:+.1}%", change * 100.0)),
+            if regressed { "REGRESSION" } else { "" }.to_string(),
+        ]);
+    }
+    if !rows.is_empty() {
+        print!(
+            "{}",
+            runner::format_columns(
+                ["Day", "Stage", "Min", "Median", "Max", "Change", ""],
+                [
+                    Align::Right,
+                    Align::Left,
+                    Align::Right,
+                    Align::Right,
+                    Align::Right,
+                    Align::Right,
+                    Align::Left
+                ],
+                &rows,
+            )
+        );
+    }
+
+    if save {
+        for result in &results {
+            baseline.insert(result);
+        }
+        baseline.save(&path)?;
+        println!("Saved baseline to {}", path.display());
+    }
+
+    if n_failures > 0 {
+        bail!("{} day(s) failed", n_failures);
+    }
+    // Saving accepts the new timings, so only complain when comparing.
+    if n_regressions > 0 && !save {
+        bail!(
+            "{} stage(s) more than {}% slower than {}",
+            n_regressions,
+            threshold * 100.0,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+// Compares the days' answers with their reference solutions, for the days that have them.
+fn cross_check(args: &[String]) -> anyhow::Result<()> {
+    let days = optional_days(args)?;
+    check_input_file(args, &days)?;
+    let overrides = overrides(args, &days)?;
+
+    let mut n_failures = 0;
+    let mut n_disagreements = 0;
+    let mut rows = Vec::new();
+    for day in days {
+        let checks = match runner::cross_check_day(day, &overrides) {
+            Ok(checks) => checks,
+            Err(err) => {
+                eprintln!("Day {} failed: {:#}", day.day(), err);
+                n_failures += 1;
+                continue;
+            }
+        };
+        for check in checks {
+            let status = match &check.reference {
+                None => "no reference",
+                Some(_) if check.disagrees() => {
+                    n_disagreements += 1;
+                    "DISAGREE"
+                }
+                Some(_) => "ok",
+            };
+            rows.push([
+                check.day.to_string(),
+                check.part.to_string(),
+                check.answer.to_string(),
+                check
+                    .reference
+                    .as_ref()
+                    .map_or(String::new(), ToString::to_string),
+                status.to_string(),
+            ]);
+        }
+    }
+    if !rows.is_empty() {
+        print!(
+            "{}",
+            runner::format_columns(
+                ["Day", "Part", "Answer", "Reference", "Status"],
+                [
+                    Align::Right,
+                    Align::Right,
+                    Align::Left,
+                    Align::Left,
+                    Align::Left
+                ],
+                &rows,
+            )
+        );
+    }
+
+    if n_disagreements > 0 || n_failures > 0 {
+        bail!(
+            "{} answer(s) disagree with the reference and {} day(s) failed",
+            n_disagreements,
+            n_failures
+        );
+    }
+    Ok(())
+}
+
+// Runs the days over every input in a directory, in parallel, and reports each file.
+fn batch(args: &[String]) -> anyhow::Result<()> {
+    let days = select_days(args.first().ok_or_else(|| anyhow!(USAGE))?)?;
+    let dir = args
+        .get(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!(USAGE))?;
+    let part = flag_value(args, "--part")?
+        .map(|part| part.parse::<usize>().context("Invalid part"))
+        .transpose()?;
+    let overrides = overrides(args, &days)?;
+
+    let files = batch::input_files(&dir)?;
+    let jobs = batch::jobs(&files, &days);
+    if jobs.is_empty() {
+        bail!("No inputs for the day(s) in {}", dir.display());
+    }
+    // Panics are reported in the table, so their messages, which could come from
+    // several threads at once, would only get in its way.
+    std::panic::set_hook(Box::new(|_| {}));
+    let results = batch::run_batch(&jobs, part, &overrides);
+    print!("{}", batch::format_batch(&results));
+
+    let n_failures = results
+        .iter()
+        .filter(|result| result.outcome.is_err())
+        .count();
+    if n_failures > 0 {
+        bail!("{} of {} input(s) failed", n_failures, results.len());
+    }
+    Ok(())
+}
+
+// Writes random inputs for the days that have generators, one file per seed.
+fn generate(args: &[String]) -> anyhow::Result<()> {
+    let selection = args.first().ok_or_else(|| anyhow!(USAGE))?;
+    let generators: Vec<&Generator> = if selection == "all" {
+        generate::generators().iter().collect()
+    } else {
+        let day = selection
+            .parse::<u8>()
+            .with_context(|| format!("Invalid day: {}", selection))?;
+        vec![generate::find_generator(day)
+            .ok_or_else(|| anyhow!("Day {} has no input generator", day))?]
+    };
+    let seed = flag_value(args, "--seed")?
+        .map(|seed| seed.parse::<u64>().context("Invalid seed"))
+        .transpose()?
+        .unwrap_or(0);
+    let count = flag_value(args, "--count")?
+        .map(|count| count.parse::<u64>().context("Invalid count"))
+        .transpose()?
+        .unwrap_or(1);
+    let dir = PathBuf::from(flag_value(args, "--out-dir")?.unwrap_or(DEFAULT_GENERATED_DIR));
+    let overrides = Overrides::from_args(args)?;
+    overrides.check_declared(generators.iter().map(|generator| generator.params))?;
+
+    std::fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+    for generator in generators {
+        let params = Params::new(generator.params, &overrides)?;
+        for seed in seed..seed + count {
+            let path = dir.join(generate::file_name(generator.day, seed));
+            std::fs::write(&path, generator.generate(seed, &params))
+                .with_context(|| format!("Writing {}", path.display()))?;
+            println!("{}", path.display());
+        }
+    }
+    Ok(())
+}
+
+// How `aoc render` draws a day.
+struct RenderOptions {
+    out: PathBuf,
+    scale: usize,
+    every: usize,
+    max_frames: usize,
+    delay: u16,
+}
+
+impl RenderOptions {
+    fn is_animation(&self) -> bool {
+        self.out
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gif"))
+    }
+}
+
+// The value of a numeric flag, or the default if it is not given.
+fn numeric_flag<T: std::str::FromStr>(args: &[String], flag: &str, default: T) -> anyhow::Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    flag_value(args, flag)?
+        .map(|value| {
+            value
+                .parse::<T>()
+                .with_context(|| format!("Invalid {}", flag))
+        })
+        .transpose()
+        .map(|value| value.unwrap_or(default))
+}
+
+// The day to simulate, which must be one of SIMULATED_DAYS, with its parameters and
+// input.
+fn simulated_day(args: &[String], command: &str) -> anyhow::Result<(u8, Params, String)> {
+    let selection = args.first().ok_or_else(|| anyhow!(USAGE))?;
+    if selection == "all" {
+        bail!("{} takes a single day", command);
+    }
+    let days = select_days(selection)?;
+    let overrides = overrides(args, &days)?;
+    let day = days[0];
+    if !SIMULATED_DAYS.contains(&day.day()) {
+        bail!(
+            "Day {} is not a simulation; try one of {:?}",
+            day.day(),
+            SIMULATED_DAYS
+        );
+    }
+    let params = Params::new(day.params(), &overrides)?;
+    let input = rust_advent::read_file_as_string(&runner::input_name(day.day()))?;
+    Ok((day.day(), params, input))
+}
+
+// Steps through a day's simulation in the terminal.
+fn play(args: &[String]) -> anyhow::Result<()> {
+    let (day, params, input) = simulated_day(args, "play")?;
+    let speed = numeric_flag(args, "--speed", simulation::DEFAULT_SPEED)?;
+    match day {
+        6 => {
+            let lab = day06::Day06::parse(&input, &params)?;
+            simulation::play(day06::GuardWalk::new(&lab), speed)
+        }
+        14 => simulation::play(day14::Day14::parse(&input, &params)?, speed),
+        15 => {
+            let (warehouse, moves) = day15::Day15::parse(&input, &params)?;
+            simulation::play(day15::RobotMoves::new(&warehouse, &moves), speed)
+        }
+        _ => unreachable!("Day {} is in SIMULATED_DAYS", day),
+    }
+}
+
+// Draws a day's simulation: its final state as an image, or its steps as a GIF.
+fn render(args: &[String]) -> anyhow::Result<()> {
+    let (day, params, input) = simulated_day(args, "render")?;
+    let options = RenderOptions {
+        out: PathBuf::from(
+            flag_value(args, "--out")?.ok_or_else(|| anyhow!("render requires --out <file>"))?,
+        ),
+        scale: numeric_flag(args, "--scale", DEFAULT_RENDER_SCALE)?,
+        every: numeric_flag(args, "--every", 1)?,
+        max_frames: numeric_flag(args, "--frames", DEFAULT_RENDER_FRAMES)?,
+        delay: numeric_flag(args, "--delay", DEFAULT_FRAME_DELAY)?,
+    };
+
+    match day {
+        6 => {
+            let lab = day06::Day06::parse(&input, &params)?;
+            if options.is_animation() {
+                save_animation(
+                    &options,
+                    day06::path_frames(&lab, options.every),
+                    &day06::palette(),
+                )
+            } else {
+                save_image(&options, &day06::path_picture(&lab), &day06::palette())
+            }
+        }
+        14 => {
+            let robots = day14::Day14::parse(&input, &params)?;
+            if options.is_animation() {
+                save_animation(
+                    &options,
+                    day14::frames(&robots, options.every),
+                    &day14::palette(),
+                )
+            } else {
+                let tree = day14::minimum_score_frame(&robots);
+                save_image(&options, &tree.to_grid(), &day14::palette())
+            }
+        }
+        15 => {
+            let (warehouse, moves) = day15::Day15::parse(&input, &params)?;
+            if options.is_animation() {
+                save_animation(
+                    &options,
+                    day15::move_frames(&warehouse, &moves, options.every),
+                    &day15::palette(),
+                )
+            } else {
+                let warehouse = day15::apply_moves(&warehouse, &moves);
+                save_image(&options, &warehouse.to_grid(), &day15::palette())
+            }
+        }
+        _ => unreachable!("Day {} is in SIMULATED_DAYS", day),
+    }
+}
+
+fn save_image<T: PartialEq>(
+    options: &RenderOptions,
+    grid: &Grid<T>,
+    palette: &Palette<T>,
+) -> anyhow::Result<()> {
+    Image::from_grid(grid, palette, options.scale).save(&options.out)?;
+    report_saved(&options.out, 1);
+    Ok(())
+}
+
+fn save_animation<T: PartialEq>(
+    options: &RenderOptions,
+    frames: impl Iterator<Item = Grid<T>>,
+    palette: &Palette<T>,
+) -> anyhow::Result<()> {
+    let mut animation = Animation::new(options.delay);
+    for frame in frames.take(options.max_frames) {
+        animation.push(Image::from_grid(&frame, palette, options.scale));
+    }
+    animation.save(&options.out)?;
+    report_saved(&options.out, animation.frames().len());
+    Ok(())
+}
+
+fn report_saved(path: &Path, n_frames: usize) {
+    if n_frames == 1 {
+        println!("{}", path.display());
+    } else {
+        println!("{} ({} frames)", path.display(), n_frames);
+    }
+}
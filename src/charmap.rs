@@ -0,0 +1,135 @@
+//! Parsing character maps with a legend that says what each glyph stands for.
+//!
+//! For example, a warehouse map with a robot standing on an empty floor:
+//!
+//! ```
+//! use rust_advent::charmap::{Glyph, Legend};
+//!
+//! let legend = Legend::new([
+//!     ('.', Glyph::Cell(false)),
+//!     ('#', Glyph::Cell(true)),
+//!     ('@', Glyph::Entity("robot", false)),
+//! ]);
+//! let map = legend.parse("#.@\n#..").unwrap();
+//! assert_eq!(map.grid.width(), 3);
+//! assert_eq!(map.position(&"robot"), Some(rust_advent::Vec2::new(2, 0)));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::grid::Point;
+use crate::{Grid, ParseError};
+
+/// What a glyph in the map stands for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Glyph<T, E> {
+    /// A plain cell.
+    Cell(T),
+    /// An entity, such as a guard or robot, standing on a cell.
+    Entity(E, T),
+}
+
+/// The glyphs that may appear in a map.
+#[derive(Clone, Debug)]
+pub struct Legend<T, E> {
+    glyphs: HashMap<char, Glyph<T, E>>,
+}
+
+/// A parsed map: the cells, and where each entity was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharMap<T, E> {
+    pub grid: Grid<T>,
+    /// The entities in row-major order.
+    pub entities: Vec<(Point, E)>,
+}
+
+impl<T: Clone, E: Clone> Legend<T, E> {
+    pub fn new(glyphs: impl IntoIterator<Item = (char, Glyph<T, E>)>) -> Self {
+        Self {
+            glyphs: glyphs.into_iter().collect(),
+        }
+    }
+
+    /// Parses the map as `Grid::parse_with` does.  Glyphs missing from the legend are
+    /// errors, as are rows of different lengths.
+    pub fn parse(&self, input: &str) -> Result<CharMap<T, E>, ParseError> {
+        let mut entities = Vec::new();
+        let grid = Grid::parse_with_points(input, |point, c| match self.glyphs.get(&c)? {
+            Glyph::Cell(cell) => Some(cell.clone()),
+            Glyph::Entity(entity, cell) => {
+                entities.push((point, entity.clone()));
+                Some(cell.clone())
+            }
+        })?;
+        Ok(CharMap { grid, entities })
+    }
+}
+
+impl<T, E: PartialEq> CharMap<T, E> {
+    /// Where the entity appears, in row-major order.
+    pub fn positions<'a>(&'a self, entity: &'a E) -> impl Iterator<Item = Point> + 'a {
+        self.entities
+            .iter()
+            .filter(move |(_, e)| e == entity)
+            .map(|&(point, _)| point)
+    }
+
+    /// The first place the entity appears.
+    pub fn position(&self, entity: &E) -> Option<Point> {
+        self.positions(entity).next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec2;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Cell {
+        Floor,
+        Wall,
+    }
+
+    fn legend() -> Legend<Cell, char> {
+        Legend::new([
+            ('.', Glyph::Cell(Cell::Floor)),
+            ('#', Glyph::Cell(Cell::Wall)),
+            ('a', Glyph::Entity('a', Cell::Floor)),
+            ('b', Glyph::Entity('b', Cell::Floor)),
+        ])
+    }
+
+    #[test]
+    fn entities_stand_on_cells() {
+        let map = legend().parse("#a.\nb.a").unwrap();
+        assert_eq!(map.grid[Vec2::new(1, 0)], Cell::Floor);
+        assert_eq!(map.grid[Vec2::new(0, 0)], Cell::Wall);
+        assert_eq!(
+            map.positions(&'a').collect::<Vec<_>>(),
+            vec![Vec2::new(1, 0), Vec2::new(2, 1)]
+        );
+        assert_eq!(map.position(&'b'), Some(Vec2::new(0, 1)));
+        assert_eq!(map.position(&'c'), None);
+    }
+
+    #[test]
+    fn unknown_glyph_is_an_error() {
+        let err = legend().parse("#a.\n#x.").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.token, "x");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: unexpected character, found `x`"
+        );
+    }
+
+    #[test]
+    fn ragged_rows_are_an_error() {
+        let err = legend().parse("#a.\n#.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected 3 columns, found end of line"
+        );
+    }
+}
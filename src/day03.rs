@@ -0,0 +1,81 @@
+// Day 3: Mull It Over
+
+use regex::Regex;
+
+use crate::{Answer, Params, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    type Input = String;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        sum_of_multiplies(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        conditional_sum_of_multiplies(input).into()
+    }
+}
+
+pub fn sum_of_multiplies(inputs: &str) -> i64 {
+    let re = Regex::new(r"mul\((\d+),(\d+)\)").expect("Failed to create regex");
+    re.captures_iter(inputs)
+        .map(|c| c.extract())
+        .map(|(_, [v1, v2])| v1.parse::<i64>().unwrap() * v2.parse::<i64>().unwrap())
+        .sum()
+}
+
+pub fn conditional_sum_of_multiplies(inputs: &str) -> i64 {
+    let mut enabled = true;
+    let re = Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").expect("Failed to create regex");
+    let mut sum = 0;
+    for capture in re.captures_iter(inputs) {
+        match &capture[0] {
+            "do()" => enabled = true,
+            "don't()" => enabled = false,
+            _ => {
+                if enabled {
+                    sum += capture[1].parse::<i64>().unwrap() * capture[2].parse::<i64>().unwrap();
+                }
+            }
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_has_sum_zero() {
+        assert_eq!(sum_of_multiplies(""), 0);
+    }
+
+    #[test]
+    fn no_match_has_sum_zero() {
+        assert_eq!(sum_of_multiplies("abcmul(b,4)cdmul(3,)mul(2, 3)"), 0);
+    }
+
+    #[test]
+    fn single_sum() {
+        assert_eq!(sum_of_multiplies("mul(11,3)"), 33);
+    }
+
+    #[test]
+    fn single_sum_enabled() {
+        assert_eq!(conditional_sum_of_multiplies("don't()do()mul(11,3)"), 33);
+    }
+
+    #[test]
+    fn single_sum_disabled() {
+        assert_eq!(conditional_sum_of_multiplies("don't()mul(11,3)"), 0);
+    }
+}
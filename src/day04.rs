@@ -0,0 +1,94 @@
+// Day 4: Ceres Search
+
+use crate::grid::Point;
+use crate::{Answer, Direction8, Grid, Params, Solution, Vec2};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+    type Input = Grid<u8>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(Grid::parse_ascii(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        count_in_grid(input, b"XMAS").into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        count_x_in_grid(input, b"MAS").into()
+    }
+}
+
+// Returns true if the character at the offset from the point is in bounds and equal
+// to the expected value.
+fn is_expected(input: &Grid<u8>, point: Point, offset: Vec2<isize>, expected: u8) -> bool {
+    input
+        .offset(point, offset)
+        .is_some_and(|point| input[point] == expected)
+}
+
+/// Counts the number of occurrences of the specified string in the grid.
+pub fn count_in_grid(inputs: &Grid<u8>, word: &[u8]) -> u32 {
+    let mut count = 0;
+    for (point, val) in inputs.iter() {
+        if *val != word[0] {
+            continue;
+        }
+
+        for direction in Direction8::ALL {
+            if word.iter().enumerate().skip(1).all(|(word_idx, word_val)| {
+                is_expected(
+                    inputs,
+                    point,
+                    direction.unit_vector() * word_idx as isize,
+                    *word_val,
+                )
+            }) {
+                count += 1
+            }
+        }
+    }
+    count
+}
+
+pub fn count_x_in_grid(inputs: &Grid<u8>, word: &[u8]) -> u32 {
+    assert!(word.len() & 1 == 1, "Word must have odd length");
+    let half_len = word.len() / 2;
+    let central_char = word[half_len];
+
+    let mut count = 0;
+    for (point, val) in inputs.iter() {
+        if *val != central_char {
+            continue;
+        }
+
+        if (
+            // nw_se
+            has_diag_word(inputs, word, point, Direction8::UpLeft) ||
+            // se_nw
+            has_diag_word(inputs, word, point, Direction8::DownRight)) &&
+            // sw_ne
+            (has_diag_word(inputs, word, point, Direction8::DownLeft) ||
+            // ne_sw
+            has_diag_word(inputs, word, point, Direction8::UpRight))
+        {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn has_diag_word(inputs: &Grid<u8>, word: &[u8], point: Point, direction: Direction8) -> bool {
+    let half_len = (word.len() / 2) as isize;
+    word.iter()
+        .cloned()
+        .enumerate()
+        .all(|(word_idx, word_val)| {
+            let distance = half_len - word_idx as isize;
+            is_expected(inputs, point, direction.unit_vector() * distance, word_val)
+        })
+}
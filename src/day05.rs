@@ -0,0 +1,195 @@
+// Day 5: Print Queue
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{Answer, Params, RulesAndUpdates, Solution};
+
+// This solves a restricted version of the problem, where we assume that the rules are complete,
+// meaning we don't have to consider non-consecutive elements of a page to determine if it is
+// consistent with the rules.
+//
+// This is not true in general.  Consider the rules 1|3 and 3|4.  By these rules, 4,1,3
+// is not valid because 3 appears before 4.  But this cannot be discovered by only considering
+// the parirs (4, 1) and (1, 3) unless we derive all the consequent rules (in this case, 1|4).
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+    type Input = RulesAndUpdates;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(crate::parse_rules_and_updates(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        sum_of_middle_values_of_valid_pages(&input.pages, &input.before).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        sum_of_corrected_middle_values(&input.pages, &input.before).into()
+    }
+
+    fn reference_part1(input: &Self::Input, _params: &Params) -> Option<Answer> {
+        Some(
+            input
+                .pages
+                .iter()
+                .filter(|p| reference_is_valid_page(p, &input.before))
+                .map(|p| p[p.len() / 2] as u32)
+                .sum::<u32>()
+                .into(),
+        )
+    }
+
+    fn reference_part2(input: &Self::Input, _params: &Params) -> Option<Answer> {
+        Some(
+            input
+                .pages
+                .iter()
+                .filter(|p| !reference_is_valid_page(p, &input.before))
+                .map(|p| reference_corrected_order(p, &input.before)[p.len() / 2] as u32)
+                .sum::<u32>()
+                .into(),
+        )
+    }
+}
+
+// Whether a rule says that page a comes before page b.  Pages that only ever come first
+// have no entry.
+fn comes_before(a: &u16, b: &u16, before: &HashMap<u16, HashSet<u16>>) -> bool {
+    before.get(b).is_some_and(|pages| pages.contains(a))
+}
+
+pub fn is_valid_page(page: &[u16], before: &HashMap<u16, HashSet<u16>>) -> bool {
+    page.iter().is_sorted_by(|a, b| comes_before(a, b, before))
+}
+
+/// Checks every pair of pages rather than only neighbouring ones, so this does not need
+/// the rules to be complete.
+pub fn reference_is_valid_page(page: &[u16], before: &HashMap<u16, HashSet<u16>>) -> bool {
+    page.iter().enumerate().all(|(idx, later)| {
+        page[..idx]
+            .iter()
+            .all(|earlier| !comes_before(later, earlier, before))
+    })
+}
+
+/// Orders the pages by repeatedly taking one that no remaining page has to come before.
+///
+/// Panics if the rules for these pages form a cycle.
+pub fn reference_corrected_order(page: &[u16], before: &HashMap<u16, HashSet<u16>>) -> Vec<u16> {
+    let mut remaining = page.to_vec();
+    let mut ordered = Vec::with_capacity(page.len());
+    while !remaining.is_empty() {
+        let idx = (0..remaining.len())
+            .find(|&idx| {
+                remaining
+                    .iter()
+                    .all(|other| !comes_before(other, &remaining[idx], before))
+            })
+            .expect("The rules should not have a cycle");
+        ordered.push(remaining.remove(idx));
+    }
+    ordered
+}
+
+pub fn sum_of_middle_values_of_valid_pages(
+    pages: &[Vec<u16>],
+    before: &HashMap<u16, HashSet<u16>>,
+) -> u32 {
+    pages
+        .iter()
+        .filter(|p| is_valid_page(p, before))
+        .map(|p| p[p.len() / 2] as u32)
+        .sum()
+}
+
+pub fn sum_of_corrected_middle_values(
+    pages: &[Vec<u16>],
+    before: &HashMap<u16, HashSet<u16>>,
+) -> u32 {
+    pages
+        .iter()
+        .filter(|p| !is_valid_page(p, before))
+        .cloned()
+        .map(|mut p| {
+            p.sort_by(|a, b| comes_before(a, b, before).cmp(&true));
+            p[p.len() / 2] as u32
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Registered, Runnable};
+    use crate::Overrides;
+    use proptest::prelude::*;
+
+    #[test]
+    fn pages_with_nothing_before_them() {
+        // No rule puts anything before 47, so it has no entry.
+        let before = HashMap::from([(53, HashSet::from([47]))]);
+        assert!(is_valid_page(&[47, 53], &before));
+        assert!(!is_valid_page(&[53, 47], &before));
+    }
+
+    // A print queue whose rules give an order for every pair of pages, as the puzzle's
+    // do, and whose updates each have an odd number of pages.
+    fn print_queue() -> impl Strategy<Value = String> {
+        (3_u16..12).prop_flat_map(|n_pages| {
+            let pages = (10..10 + n_pages).collect::<Vec<_>>();
+            let update = (
+                Just(pages.clone()).prop_shuffle(),
+                0..(n_pages as usize).div_ceil(2),
+            )
+                .prop_map(|(pages, half)| pages[..2 * half + 1].to_vec());
+            (
+                Just(pages).prop_shuffle(),
+                prop::collection::vec(update, 1..8),
+            )
+                .prop_map(|(order, updates)| {
+                    let mut input = String::new();
+                    for (idx, a) in order.iter().enumerate() {
+                        for b in &order[idx + 1..] {
+                            input += &format!("{}|{}\n", a, b);
+                        }
+                    }
+                    input.push('\n');
+                    for update in updates {
+                        input += &update
+                            .iter()
+                            .map(u16::to_string)
+                            .collect::<Vec<_>>()
+                            .join(",");
+                        input.push('\n');
+                    }
+                    input
+                })
+        })
+    }
+
+    #[test]
+    fn reference_does_not_need_complete_rules() {
+        // With only 1|3 and 3|4, 1,2,3 is in order, though no rule mentions page 2.
+        let before = HashMap::from([(3, HashSet::from([1])), (4, HashSet::from([3]))]);
+        assert!(!is_valid_page(&[1, 2, 3], &before));
+        assert!(reference_is_valid_page(&[1, 2, 3], &before));
+        assert!(!reference_is_valid_page(&[4, 1, 3], &before));
+        assert_eq!(
+            reference_corrected_order(&[4, 1, 3], &before),
+            vec![1, 3, 4]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_reference(input in print_queue()) {
+            let checks = Registered::<Day05>::new().cross_check(&input, &Overrides::default()).unwrap();
+            for check in checks {
+                prop_assert!(!check.disagrees(), "{:?}", check);
+            }
+        }
+    }
+}
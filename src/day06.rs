@@ -0,0 +1,311 @@
+// Day 6: Guard Gallivant
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail};
+
+use crate::charmap::{Glyph, Legend};
+use crate::render::Palette;
+use crate::simulation::Simulation;
+use crate::{Answer, Direction4, Grid, Params, Solution, Vec2};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+    type Input = Lab;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        parse_lab(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        count_visited_squares(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        count_looping_obstacles(input).into()
+    }
+}
+
+pub fn count_visited_squares(lab: &Lab) -> i32 {
+    squares_covered_by_guard(lab).len() as i32
+}
+
+pub fn squares_covered_by_guard(grid: &Lab) -> HashSet<Point> {
+    walk(grid).map(|guard| guard.location).collect()
+}
+
+pub fn count_looping_obstacles(lab: &Lab) -> i32 {
+    let mut grid = lab.clone();
+    let visited = squares_covered_by_guard(&grid);
+    if visited.is_empty() {
+        return 0;
+    }
+
+    let mut n_obstacles_that_cause_loop = 0;
+    for position in visited {
+        grid.obstacles.insert(position);
+        if is_guard_in_loop(&grid) {
+            n_obstacles_that_cause_loop += 1;
+        }
+        grid.obstacles.remove(&position);
+    }
+    n_obstacles_that_cause_loop
+}
+
+pub fn is_guard_in_loop(grid: &Lab) -> bool {
+    // This time we need to track the direction as well: returning to a previous state
+    // means the guard is in a loop, while walking out of the lab means they were not.
+    let mut visited = HashSet::<Guard>::new();
+    !walk(grid).all(|guard| visited.insert(guard))
+}
+
+// The guard after each move or turn, starting where they stand, until they leave the
+// lab.  Never ends if the guard is stuck in a loop.
+fn walk(lab: &Lab) -> impl Iterator<Item = Guard> + '_ {
+    std::iter::successors(Some(lab.guard_initial_position), |&guard| lab.step(guard))
+}
+
+/// The guard walking through the lab, one move or turn at a time, until they leave it.
+#[derive(Clone)]
+pub struct GuardWalk {
+    lab: Lab,
+    guard: Option<Guard>,
+    visited: HashSet<Point>,
+}
+
+impl GuardWalk {
+    pub fn new(lab: &Lab) -> Self {
+        let guard = lab.guard_initial_position;
+        Self {
+            lab: lab.clone(),
+            guard: Some(guard),
+            visited: HashSet::from([guard.location]),
+        }
+    }
+
+    /// The number of distinct squares visited so far.
+    pub fn n_visited(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+impl Simulation for GuardWalk {
+    fn step(&mut self) -> bool {
+        let Some(guard) = self.guard else {
+            return false;
+        };
+        self.guard = self.lab.step(guard);
+        if let Some(guard) = self.guard {
+            self.visited.insert(guard.location);
+        }
+        true
+    }
+
+    /// The lab as in the puzzle, with the visited squares marked X.
+    fn render(&self) -> String {
+        let picture = self
+            .lab
+            .picture(&self.visited, self.guard.map(|guard| guard.location));
+        let text = picture.map(|cell| match cell {
+            LabCell::Floor => '.',
+            LabCell::Obstacle => '#',
+            LabCell::Visited => 'X',
+            LabCell::Guard => self.guard.map_or('X', |guard| guard.facing.arrow()),
+        });
+        format!("{}{} squares visited\n", text, self.n_visited())
+    }
+}
+
+/// What a square of the lab shows in a picture of the guard's path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabCell {
+    Floor,
+    Obstacle,
+    Visited,
+    Guard,
+}
+
+/// The colours of the guard's path: the floor is dark, the obstacles grey, the path
+/// blue and the guard red.
+pub fn palette() -> Palette<LabCell> {
+    Palette::new(
+        [16, 16, 32],
+        [
+            (LabCell::Obstacle, [160, 160, 160]),
+            (LabCell::Visited, [70, 110, 220]),
+            (LabCell::Guard, [230, 40, 40]),
+        ],
+    )
+}
+
+/// The lab with every square the guard visits before leaving it marked.
+pub fn path_picture(lab: &Lab) -> Grid<LabCell> {
+    lab.picture(&squares_covered_by_guard(lab), None)
+}
+
+/// The guard walking through the lab, drawn when they start, after every `every` moves
+/// or turns, and when they are about to leave.  Never ends if the guard is stuck in a
+/// loop.
+pub fn path_frames(lab: &Lab, every: usize) -> impl Iterator<Item = Grid<LabCell>> + '_ {
+    let every = every.max(1);
+    let mut visited = HashSet::new();
+    let mut guards = walk(lab).enumerate().peekable();
+    std::iter::from_fn(move || loop {
+        let (step, guard) = guards.next()?;
+        visited.insert(guard.location);
+        if step.is_multiple_of(every) || guards.peek().is_none() {
+            return Some(lab.picture(&visited, Some(guard.location)));
+        }
+    })
+}
+
+/// The lab's size and obstacles, and where the guard starts.
+#[derive(Clone)]
+pub struct Lab {
+    width: i32,
+    height: i32,
+    obstacles: HashSet<Point>,
+    guard_initial_position: Guard,
+}
+
+impl Lab {
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        (0..self.width).contains(&point.x) && (0..self.height).contains(&point.y)
+    }
+
+    // The guard after their next move or turn, or None if they walk out of the lab.
+    fn step(&self, mut guard: Guard) -> Option<Guard> {
+        let next_position = guard.get_next_move();
+        if !self.contains(next_position) {
+            return None;
+        }
+        if self.obstacles.contains(&next_position) {
+            guard.turn_right();
+        } else {
+            guard.location = next_position;
+        }
+        Some(guard)
+    }
+
+    fn picture(&self, visited: &HashSet<Point>, guard: Option<Point>) -> Grid<LabCell> {
+        Grid::from_fn(self.width as usize, self.height as usize, |point| {
+            let point = Point::new(point.x as i32, point.y as i32);
+            if guard == Some(point) {
+                LabCell::Guard
+            } else if self.obstacles.contains(&point) {
+                LabCell::Obstacle
+            } else if visited.contains(&point) {
+                LabCell::Visited
+            } else {
+                LabCell::Floor
+            }
+        })
+    }
+}
+
+fn parse_lab(input: &str) -> anyhow::Result<Lab> {
+    let legend = Legend::new(
+        [('.', Glyph::Cell(false)), ('#', Glyph::Cell(true))]
+            .into_iter()
+            .chain(Direction4::ALL.map(|facing| (facing.arrow(), Glyph::Entity(facing, false)))),
+    );
+    let map = legend.parse(input)?;
+    let to_point = |point: Vec2<usize>| {
+        point
+            .try_cast::<i32>()
+            .ok_or_else(|| anyhow!("Map too large"))
+    };
+
+    let (location, facing) = match map.entities[..] {
+        [guard] => guard,
+        [] => bail!("No guard found"),
+        _ => bail!("More than one guard found"),
+    };
+    Ok(Lab {
+        width: map.grid.width() as i32,
+        height: map.grid.height() as i32,
+        obstacles: map
+            .grid
+            .iter()
+            .filter(|(_, &is_obstacle)| is_obstacle)
+            .map(|(point, _)| to_point(point))
+            .collect::<anyhow::Result<_>>()?,
+        guard_initial_position: Guard {
+            location: to_point(location)?,
+            facing,
+        },
+    })
+}
+
+/// Numbered from the upper left corner.
+pub type Point = Vec2<i32>;
+
+#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
+struct Guard {
+    location: Point,
+    facing: Direction4,
+}
+
+impl Guard {
+    fn get_next_move(&self) -> Point {
+        self.location + self.facing.unit_vector()
+    }
+
+    fn turn_right(&mut self) {
+        self.facing = self.facing.turn_right();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_follow_the_guard_out_of_the_lab() {
+        let lab = parse_lab(".#.\n...\n.^.").unwrap();
+        let frames = path_frames(&lab, 1).collect::<Vec<_>>();
+        // Up, turn, right, and then they walk out of the lab.
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0][Vec2::new(1, 2)], LabCell::Guard);
+        assert_eq!(frames[3][Vec2::new(2, 1)], LabCell::Guard);
+        assert_eq!(frames[3][Vec2::new(1, 1)], LabCell::Visited);
+        assert_eq!(path_frames(&lab, 2).count(), 3);
+
+        let picture = path_picture(&lab);
+        assert_eq!(picture[Vec2::new(1, 0)], LabCell::Obstacle);
+        assert_eq!(picture[Vec2::new(2, 1)], LabCell::Visited);
+        assert_eq!(picture[Vec2::new(0, 0)], LabCell::Floor);
+    }
+
+    #[test]
+    fn there_is_exactly_one_guard() {
+        let err = parse_lab(".^.\n.<.").err().unwrap();
+        assert_eq!(err.to_string(), "More than one guard found");
+        assert!(parse_lab("...\n.#.").is_err());
+    }
+
+    #[test]
+    fn walk_steps_until_the_guard_leaves() {
+        let lab = parse_lab(".#.\n...\n.^.").unwrap();
+        let mut walk = GuardWalk::new(&lab);
+        assert!(walk.step());
+        assert!(walk.step());
+        assert!(walk.render().starts_with(".#.\n.>.\n.X.\n"));
+        assert!(walk.step());
+        assert!(walk.step());
+        assert!(!walk.step());
+        assert_eq!(walk.n_visited(), 3);
+        assert!(walk.render().starts_with(".#.\n.XX\n.X.\n"));
+    }
+}
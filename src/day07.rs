@@ -0,0 +1,96 @@
+// Day 7: Bridge repair.
+
+use crate::number_theory::checked_concat_digits;
+use crate::par::prelude::*;
+use crate::parser::{integer, lines, literal, pair, parse_all, separated, Parser};
+use crate::{Answer, Params, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+    type Input = Vec<NumberPuzzle>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(parse_all(&grammar(), input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        input
+            .par_iter()
+            .filter(|puzzle| is_solveable(puzzle))
+            .map(|puzzle| puzzle.goal)
+            .sum::<i64>()
+            .into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        input
+            .par_iter()
+            .filter(|puzzle| is_solveable_with_concat(puzzle))
+            .map(|puzzle| puzzle.goal)
+            .sum::<i64>()
+            .into()
+    }
+}
+
+/// An equation whose operators are missing: the numbers, combined left to right, should
+/// give the goal.
+pub struct NumberPuzzle {
+    pub goal: i64,
+    pub numbers: Vec<i64>,
+}
+
+// Each line is like `3267: 81 40 27`.
+fn grammar() -> impl Parser<Output = Vec<NumberPuzzle>> {
+    let numbers = separated(integer(), literal(" "));
+    lines(pair(integer(), ": ", numbers).map(|(goal, numbers)| NumberPuzzle { goal, numbers }))
+}
+
+pub fn is_solveable(puzzle: &NumberPuzzle) -> bool {
+    fn is_solveable_inner(goal: i64, current: i64, items: &[i64]) -> bool {
+        if current > goal {
+            return false;
+        }
+        if items.is_empty() {
+            return goal == current;
+        }
+
+        // Using +.
+        is_solveable_inner(goal, current + items[0], &items[1..])
+        // Using *.
+        || is_solveable_inner(goal, current * items[0], &items[1..])
+    }
+
+    if puzzle.numbers.is_empty() {
+        false
+    } else {
+        is_solveable_inner(puzzle.goal, puzzle.numbers[0], &puzzle.numbers[1..])
+    }
+}
+
+pub fn is_solveable_with_concat(puzzle: &NumberPuzzle) -> bool {
+    fn is_solveable_inner(goal: i64, current: i64, items: &[i64]) -> bool {
+        if current > goal {
+            return false;
+        }
+        if items.is_empty() {
+            return goal == current;
+        }
+
+        // Using +.
+        is_solveable_inner(goal, current + items[0], &items[1..])
+        // Using *.
+        || is_solveable_inner(goal, current * items[0], &items[1..])
+        // Using concat, which is too big for the goal if it overflows.
+        || checked_concat_digits(current, items[0], 10)
+            .is_some_and(|next| is_solveable_inner(goal, next, &items[1..]))
+    }
+
+    if puzzle.numbers.is_empty() {
+        false
+    } else {
+        is_solveable_inner(puzzle.goal, puzzle.numbers[0], &puzzle.numbers[1..])
+    }
+}
@@ -0,0 +1,122 @@
+// Day 8: Resonant Collinearity
+
+use std::collections::{HashMap, HashSet};
+
+use crate::charmap::{Glyph, Legend};
+use crate::{Answer, Params, ParseError, Solution, Vec2};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+    type Input = ArrayGrid;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(create_arrays(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        get_single_antinodes(input).len().into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        get_multiple_antinodes(input).len().into()
+    }
+}
+
+pub type Point = Vec2<i32>;
+
+/// The antennas of each frequency, and the size of the map they are on.
+pub struct ArrayGrid {
+    height: usize,
+    width: usize,
+    arrays: HashMap<char, Vec<Point>>,
+}
+
+impl ArrayGrid {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The positions of the antennas, by frequency.
+    pub fn arrays(&self) -> &HashMap<char, Vec<Point>> {
+        &self.arrays
+    }
+
+    pub fn is_in_bounds(&self, point: Point) -> bool {
+        point.x >= 0 && point.x < self.width as i32 && point.y >= 0 && point.y < self.height as i32
+    }
+}
+
+fn create_arrays(input: &str) -> Result<ArrayGrid, ParseError> {
+    let antennas = ('a'..='z').chain('A'..='Z').chain('0'..='9');
+    let legend = Legend::new(
+        std::iter::once(('.', Glyph::Cell(()))).chain(antennas.map(|c| (c, Glyph::Entity(c, ())))),
+    );
+    let map = legend.parse(input)?;
+    let mut arrays = HashMap::<char, Vec<Point>>::new();
+    for (point, c) in map.entities {
+        arrays.entry(c).or_default().push(point.map(|v| v as i32));
+    }
+    Ok(ArrayGrid {
+        height: map.grid.height(),
+        width: map.grid.width(),
+        arrays,
+    })
+}
+
+pub fn get_single_antinodes(grid: &ArrayGrid) -> HashSet<Point> {
+    let mut antinodes = HashSet::new();
+    for nodes_for_char in grid.arrays.values() {
+        for first_idx in 0..(nodes_for_char.len() - 1) {
+            let first = nodes_for_char[first_idx];
+            for &second in &nodes_for_char[(first_idx + 1)..] {
+                let delta = second - first;
+
+                let first_antinode = second + delta;
+                if grid.is_in_bounds(first_antinode) {
+                    antinodes.insert(first_antinode);
+                }
+
+                let second_antinode = first - delta;
+                if grid.is_in_bounds(second_antinode) {
+                    antinodes.insert(second_antinode);
+                }
+            }
+        }
+    }
+    antinodes
+}
+
+pub fn get_multiple_antinodes(grid: &ArrayGrid) -> HashSet<Point> {
+    let mut antinodes = HashSet::new();
+    for nodes_for_char in grid.arrays.values() {
+        for first_idx in 0..(nodes_for_char.len() - 1) {
+            let first = nodes_for_char[first_idx];
+            for &second in &nodes_for_char[(first_idx + 1)..] {
+                // Every grid point in line with the pair is an antinode, including
+                // the antennas themselves, so step along the line by the smallest
+                // displacement that lands on grid points.
+                let step = (second - first).reduced();
+
+                let mut antinode = first;
+                while grid.is_in_bounds(antinode) {
+                    antinodes.insert(antinode);
+                    antinode += step;
+                }
+
+                let mut antinode = first - step;
+                while grid.is_in_bounds(antinode) {
+                    antinodes.insert(antinode);
+                    antinode -= step;
+                }
+            }
+        }
+    }
+    antinodes
+}
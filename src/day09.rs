@@ -0,0 +1,404 @@
+// Day 9: Disk Fragmenter
+
+use crate::{Answer, Params, ParseError, Solution};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+    type Input = String;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(parse_disk_map(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        defrag_and_checksum(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        defrag_and_checksum_files(input).into()
+    }
+
+    fn reference_part1(input: &Self::Input, _params: &Params) -> Option<Answer> {
+        Some(reference_defrag_and_checksum(input).into())
+    }
+
+    fn reference_part2(input: &Self::Input, _params: &Params) -> Option<Answer> {
+        Some(reference_defrag_and_checksum_files(input).into())
+    }
+}
+
+// The disk map is a single line of digits, though it may be wrapped.
+fn parse_disk_map(input: &str) -> Result<String, ParseError> {
+    let mut digits = String::new();
+    for (line_no, line) in (1..).zip(input.lines()) {
+        for (idx, c) in line.trim_end().char_indices() {
+            if !c.is_ascii_digit() {
+                return Err(ParseError::at(
+                    line_no,
+                    line,
+                    idx,
+                    &c.to_string(),
+                    "expected a digit",
+                ));
+            }
+            digits.push(c);
+        }
+    }
+    Ok(digits)
+}
+
+pub fn defrag_and_checksum(input: &str) -> u64 {
+    let mut disk = Disk::new_from_string(input);
+    disk.defrag();
+    disk.checksum()
+}
+
+pub fn defrag_and_checksum_files(input: &str) -> u64 {
+    let mut disk = DiskList::new_from_string(input);
+    disk.defrag();
+    disk.checksum()
+}
+
+/// Moves single blocks like `defrag_and_checksum`, but on a plain list of blocks, and
+/// sums over every position of the disk.
+pub fn reference_defrag_and_checksum(input: &str) -> u64 {
+    let mut blocks = reference_blocks(input);
+    while let Some(free) = blocks.iter().position(Option::is_none) {
+        let last = blocks.iter().rposition(Option::is_some);
+        match last {
+            Some(last) if last > free => blocks.swap(free, last),
+            _ => break,
+        }
+    }
+    reference_checksum(&blocks)
+}
+
+/// Moves whole files like `defrag_and_checksum_files`, but on a plain list of blocks,
+/// searching the whole disk for free space instead of keeping track of it.
+pub fn reference_defrag_and_checksum_files(input: &str) -> u64 {
+    let mut blocks = reference_blocks(input);
+    let n_files = input.len().div_ceil(2);
+    for id in (0..n_files).rev() {
+        let Some(start) = blocks.iter().position(|&block| block == Some(id)) else {
+            continue;
+        };
+        let size = blocks[start..]
+            .iter()
+            .take_while(|&&block| block == Some(id))
+            .count();
+        if let Some(free) = blocks[..start]
+            .windows(size)
+            .position(|window| window.iter().all(Option::is_none))
+        {
+            for offset in 0..size {
+                blocks.swap(free + offset, start + offset);
+            }
+        }
+    }
+    reference_checksum(&blocks)
+}
+
+// The file id in each block, or None if the block is free.
+fn reference_blocks(input: &str) -> Vec<Option<usize>> {
+    input
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .flat_map(|(idx, size)| {
+            let id = (idx % 2 == 0).then_some(idx / 2);
+            std::iter::repeat_n(id, size as usize)
+        })
+        .collect()
+}
+
+fn reference_checksum(blocks: &[Option<usize>]) -> u64 {
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(position, id)| id.map(|id| (position * id) as u64))
+        .sum()
+}
+
+/// The disk block by block, for moving single blocks in part 1.
+pub struct Disk {
+    // The value u16::MAX is used to represent an empty slot.
+    blocks: Vec<u16>,
+}
+
+impl Disk {
+    pub fn new_from_string(input: &str) -> Self {
+        let elems: Vec<u32> = input.chars().filter_map(|c| c.to_digit(10)).collect();
+        let n_blocks = elems.iter().sum::<u32>() as usize;
+        let mut blocks = vec![u16::MAX; n_blocks];
+
+        enum ParseState {
+            Empty,
+            Data,
+        }
+        let mut state = ParseState::Data;
+        let mut id = 0;
+        let mut idx = 0;
+        for elem in elems {
+            match state {
+                ParseState::Empty => {
+                    // Block is already initialized to the empty value, so just skip ahead.
+                    state = ParseState::Data;
+                }
+                ParseState::Data => {
+                    blocks[idx..(idx + elem as usize)].fill(id as u16);
+                    id += 1;
+                    state = ParseState::Empty;
+                }
+            }
+            idx += elem as usize;
+        }
+        Self { blocks }
+    }
+
+    pub fn defrag(&mut self) {
+        let mut l = 0;
+        let mut r = self.blocks.len() - 1;
+        while l < r {
+            if self.blocks[r] == u16::MAX {
+                r -= 1;
+            } else if self.blocks[l] != u16::MAX {
+                l += 1;
+            } else {
+                self.blocks[l] = self.blocks[r];
+                self.blocks[r] = u16::MAX;
+                l += 1;
+                r -= 1;
+            }
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.blocks
+            .iter()
+            .cloned()
+            .filter(|b| *b != u16::MAX)
+            .enumerate()
+            .map(|(i, b)| i * (b as usize))
+            .sum::<usize>() as u64
+    }
+}
+
+/// The disk as lists of files and free spans, for moving whole files in part 2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskList {
+    files: Vec<File>,
+    free_space: Vec<FreeSpace>,
+}
+
+impl DiskList {
+    pub fn new_from_string(input: &str) -> Self {
+        let elems: Vec<u32> = input.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        let mut files = Vec::with_capacity(elems.len() / 2 + 1);
+        let mut free_space = Vec::with_capacity(elems.len() / 2);
+        enum ParseState {
+            Empty,
+            Data,
+        }
+        let mut state = ParseState::Data;
+        let mut id = 0;
+        let mut idx = 0;
+        for elem in elems {
+            match state {
+                ParseState::Empty => {
+                    free_space.push(FreeSpace {
+                        start: idx,
+                        size: elem as usize,
+                    });
+                    state = ParseState::Data;
+                }
+                ParseState::Data => {
+                    files.push(File {
+                        id,
+                        start: idx,
+                        size: elem as usize,
+                    });
+                    id += 1;
+                    state = ParseState::Empty;
+                }
+            }
+            idx += elem as usize;
+        }
+
+        Self { files, free_space }
+    }
+
+    pub fn defrag(&mut self) {
+        self.files.iter_mut().rev().for_each(|fl| {
+            if let Some(free) = self
+                .free_space
+                .iter_mut()
+                .find(|free| free.start < fl.start && free.size >= fl.size)
+            {
+                // We found a free chunk to move the file into.
+                // Do so, reducing the free space chunk.
+                fl.start = free.start;
+                free.start += fl.size;
+                free.size -= fl.size;
+
+                // This implementation is not quite right -- we need to create a new free space chunk
+                // in the space we moved out of, and possibly merge with adjacent free space chunks.
+                // However, that doesn't affect the answer:
+                //  1) The checksum doesn't care about the free space.
+                //  2) Because we are iterating backwards over the files, no other file can move
+                //    into the space we just moved out of.
+            }
+        });
+
+        self.files.sort_by_key(|fl| fl.start);
+        self.free_space.retain(|free| free.size > 0);
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.files.iter().map(|fl| fl.checksum()).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct File {
+    id: u16,
+    start: usize,
+    // A size of 0 means the file is deactivated.
+    size: usize,
+}
+
+impl File {
+    fn checksum(&self) -> u64 {
+        if self.size == 0 {
+            0
+        } else {
+            // There is surely a closed form for this.
+            (self.start..(self.start + self.size))
+                .map(|idx| idx as u64 * self.id as u64)
+                .sum()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FreeSpace {
+    start: usize,
+    size: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Registered, Runnable};
+    use crate::Overrides;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_rejects_stray_characters() {
+        assert_eq!(parse_disk_map("2333\n1331 \n").unwrap(), "23331331");
+        let err = parse_disk_map("2333\n13x1").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn test_new_from_string() {
+        let disk = Disk::new_from_string("2333133121414131402");
+        assert_eq!(
+            disk.blocks,
+            vec![
+                0,
+                0,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                1,
+                1,
+                1,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                2,
+                u16::MAX,
+                u16::MAX,
+                u16::MAX,
+                3,
+                3,
+                3,
+                u16::MAX,
+                4,
+                4,
+                u16::MAX,
+                5,
+                5,
+                5,
+                5,
+                u16::MAX,
+                6,
+                6,
+                6,
+                6,
+                u16::MAX,
+                7,
+                7,
+                7,
+                u16::MAX,
+                8,
+                8,
+                8,
+                8,
+                9,
+                9
+            ]
+        );
+    }
+
+    #[test]
+    fn test_defrag() {
+        let mut disk = Disk::new_from_string("2333133121414131402");
+        disk.defrag();
+
+        let mut expected = vec![
+            0, 0, 9, 9, 8, 1, 1, 1, 8, 8, 8, 2, 7, 7, 7, 3, 3, 3, 6, 4, 4, 6, 5, 5, 5, 5, 6, 6,
+        ];
+        expected.extend(vec![u16::MAX; 14]);
+        assert_eq!(disk.blocks, expected);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut disk = Disk::new_from_string("2333133121414131402");
+        disk.defrag();
+        assert_eq!(disk.checksum(), 1928);
+    }
+
+    #[test]
+    fn test_defrag_files() {
+        let mut disk = DiskList::new_from_string("2333133121414131402");
+        disk.defrag();
+        assert_eq!(disk.checksum(), 2858);
+    }
+
+    #[test]
+    fn reference_example() {
+        assert_eq!(reference_defrag_and_checksum("2333133121414131402"), 1928);
+        assert_eq!(
+            reference_defrag_and_checksum_files("2333133121414131402"),
+            2858
+        );
+    }
+
+    proptest! {
+        // Files take up at least one block, while free space may be empty.
+        #[test]
+        fn agrees_with_reference(sizes in prop::collection::vec((1_u8..=9, 0_u8..=9), 1..40)) {
+            let input = sizes.iter().map(|(file, free)| format!("{}{}", file, free)).collect::<String>();
+            let checks = Registered::<Day09>::new().cross_check(&input, &Overrides::default()).unwrap();
+            for check in checks {
+                prop_assert!(!check.disagrees(), "{}: {:?}", input, check);
+            }
+        }
+    }
+}
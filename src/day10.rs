@@ -0,0 +1,73 @@
+// Day 10: Hoof It
+
+use crate::grid::Point;
+use crate::{search, Answer, Grid, Params, ParseError, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+    type Input = Grid<u8>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(parse_heights(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        count_trails_by_distinct_endpoint(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        count_distinct_trails(input).into()
+    }
+}
+
+fn parse_heights(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse_with(input, |c| c.to_digit(10).map(|d| d as u8))
+}
+
+// The points one step higher than the point.
+fn uphill<'a>(grid: &'a Grid<u8>, point: &Point) -> impl Iterator<Item = Point> + 'a {
+    let height = grid[*point];
+    grid.neighbours4(*point)
+        .filter(move |&next| grid[next] == height + 1)
+}
+
+pub fn trailheads(grid: &Grid<u8>) -> impl Iterator<Item = Point> + '_ {
+    grid.iter()
+        .filter(|(_, &height)| height == 0)
+        .map(|(point, _)| point)
+}
+
+/// Counts the number of trails with distinct endpoints.
+///
+/// A trail starts at height 0 and ends at height 9,
+/// and must increase by 1 in height at each step.
+pub fn count_trails_by_distinct_endpoint(grid: &Grid<u8>) -> u32 {
+    trailheads(grid)
+        .map(|start| {
+            search::reachable([start], |point| uphill(grid, point))
+                .into_iter()
+                .filter(|&point| grid[point] == 9)
+                .count() as u32
+        })
+        .sum()
+}
+
+/// Counts the number of distinct trails.
+///
+/// A trail starts at height 0 and ends at height 9,
+/// and must increase by 1 in height at each step.
+pub fn count_distinct_trails(grid: &Grid<u8>) -> u64 {
+    // Heights always increase along a trail, so there are no cycles.
+    trailheads(grid)
+        .map(|start| {
+            search::count_paths(
+                start,
+                |point| uphill(grid, point),
+                |&point| grid[point] == 9,
+            )
+        })
+        .sum()
+}
@@ -0,0 +1,194 @@
+// Day 11: Plutonian pebbles
+
+use std::collections::HashMap;
+
+use crate::number_theory::{digit_len, split_digits};
+use crate::{Answer, Param, Params, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "part1_steps",
+            default: 25,
+            min: 0,
+            // Part 1 lists every pebble, so more blinks run out of memory.
+            max: 35,
+            description: "Number of blinks in part 1",
+        },
+        Param {
+            name: "part2_steps",
+            default: 75,
+            min: 0,
+            // Much beyond this, the count overflows.
+            max: 90,
+            description: "Number of blinks in part 2",
+        },
+    ];
+    type Input = Vec<u64>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(crate::parse_numbers_with_whitespace(input)?)
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        pebbles_after(input, params.get("part1_steps")).len().into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        count_pebbles(input, params.get("part2_steps")).into()
+    }
+
+    // Listing the pebbles, as part 1 does, checks the memoised count.
+    fn reference_part2(input: &Self::Input, params: &Params) -> Option<Answer> {
+        let n_steps = params.get("part2_steps");
+        (n_steps <= MAX_LISTED_STEPS).then(|| pebbles_after(input, n_steps).len().into())
+    }
+}
+
+/// Beyond this many blinks, listing the pebbles takes much longer than part 1 does.
+pub const MAX_LISTED_STEPS: usize = 25;
+
+/// Every pebble after the number of blinks.
+pub fn pebbles_after(numbers: &[u64], n_steps: usize) -> Vec<u64> {
+    // The iterator starts after the first blink.
+    std::iter::once(numbers.to_vec())
+        .chain(PebbleIterator::new(numbers.to_vec()))
+        .nth(n_steps)
+        .expect("The pebbles change forever")
+}
+
+/// For part 1, we actually explicitly construct the list of stones.
+pub struct PebbleIterator {
+    numbers: Vec<u64>,
+}
+
+impl PebbleIterator {
+    pub fn new(numbers: Vec<u64>) -> Self {
+        Self { numbers }
+    }
+}
+
+impl Iterator for PebbleIterator {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut new_state = Vec::with_capacity(2 * self.numbers.len());
+        for n in self.numbers.iter().cloned() {
+            if n == 0 {
+                new_state.push(1);
+                continue;
+            }
+
+            let numlen = digit_len(n, 10);
+            if numlen & 1 == 0 {
+                // Even.
+                let (left, right) = split_digits(n, numlen / 2, 10);
+                new_state.push(left);
+                new_state.push(right);
+            } else {
+                new_state.push(n * 2024);
+            }
+        }
+        self.numbers = new_state;
+        Some(self.numbers.clone())
+    }
+}
+
+// For part 2, constructing the list is impractical, so we
+// just use a recursive function with memoization to count
+// the pebbles.
+
+pub fn count_pebbles(numbers: &[u64], n_steps: usize) -> u64 {
+    fn count_pebbles_inner(value: u64, step: usize, memo: &mut HashMap<(u64, usize), u64>) -> u64 {
+        if step == 0 {
+            // Last step, we have 1 pebble.
+            return 1;
+        }
+
+        // See if we already computed this.
+        if let Some(&cached_result) = memo.get(&(value, step)) {
+            return cached_result;
+        }
+
+        // Recurse to compute.
+        let result = if value == 0 {
+            count_pebbles_inner(1, step - 1, memo)
+        } else if digit_len(value, 10) & 1 == 0 {
+            let (left, right) = split_digits(value, digit_len(value, 10) / 2, 10);
+            count_pebbles_inner(left, step - 1, memo) + count_pebbles_inner(right, step - 1, memo)
+        } else {
+            count_pebbles_inner(value * 2024, step - 1, memo)
+        };
+        memo.insert((value, step), result);
+        result
+    }
+
+    let mut memo = HashMap::new();
+    numbers
+        .iter()
+        .cloned()
+        .map(|n| count_pebbles_inner(n, n_steps, &mut memo))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Registered, Runnable};
+    use crate::Overrides;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_advance_pebble_iterator() {
+        let mut pebble_iterator = PebbleIterator::new(vec![125, 17]);
+
+        assert_eq!(pebble_iterator.next(), Some(vec![253000, 1, 7]));
+        assert_eq!(pebble_iterator.next(), Some(vec![253, 0, 2024, 14168]));
+        assert_eq!(
+            pebble_iterator.next(),
+            Some(vec![512072, 1, 20, 24, 28676032])
+        );
+        assert_eq!(
+            pebble_iterator.next(),
+            Some(vec![512, 72, 2024, 2, 0, 2, 4, 2867, 6032])
+        );
+        assert_eq!(
+            pebble_iterator.next(),
+            Some(vec![
+                1036288, 7, 2, 20, 24, 4048, 1, 4048, 8096, 28, 67, 60, 32
+            ])
+        );
+        assert_eq!(
+            pebble_iterator.next(),
+            Some(vec![
+                2097446912, 14168, 4048, 2, 0, 2, 4, 40, 48, 2024, 40, 48, 80, 96, 2, 8, 6, 7, 6,
+                0, 3, 2
+            ])
+        );
+    }
+
+    #[test]
+    fn test_count_pebbles_iterator() {
+        let mut pebble_iterator = PebbleIterator::new(vec![125, 17]);
+        assert_eq!(pebble_iterator.nth(24).unwrap().len(), 55312);
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_reference(numbers in prop::collection::vec(0_u64..100_000, 1..6), n_steps in 0_i64..20) {
+            let input = numbers.iter().map(u64::to_string).collect::<Vec<_>>().join(" ");
+            let mut overrides = Overrides::default();
+            overrides.insert("part1_steps", n_steps);
+            overrides.insert("part2_steps", n_steps);
+            let checks = Registered::<Day11>::new().cross_check(&input, &overrides).unwrap();
+            prop_assert!(checks[1].reference.is_some());
+            for check in checks {
+                prop_assert!(!check.disagrees(), "{:?}", check);
+            }
+        }
+    }
+}
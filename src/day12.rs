@@ -0,0 +1,470 @@
+// Day 12: Garden Groups
+
+use std::collections::HashSet;
+
+use crate::{search, Answer, Direction4, Grid, Params, Solution, Vec2};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+    type Input = Grid<u8>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(Grid::parse_ascii(input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        find_cost(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        find_discount_cost(input).into()
+    }
+}
+
+/// Cells, and the corners between them: the corner at (x, y) is the top left corner of
+/// the cell at (x, y).
+pub type Point = Vec2<i16>;
+
+/// A connected set of cells with the same plant.
+pub struct Region {
+    cells: HashSet<Point>,
+}
+
+impl Region {
+    pub fn cells(&self) -> &HashSet<Point> {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn perimeter(&self) -> u16 {
+        self.cells
+            .iter()
+            .map(|point| {
+                Direction4::ALL
+                    .iter()
+                    .filter(|direction| !self.cells.contains(&(*point + direction.unit_vector())))
+                    .count() as u16
+            })
+            .sum()
+    }
+
+    /// The area times the perimeter.
+    pub fn cost(&self) -> u32 {
+        self.cells.len() as u32 * self.perimeter() as u32
+    }
+
+    /// The area times the number of straight sides.
+    pub fn discount_cost(&self) -> u32 {
+        let edges = self.get_edges();
+        let n_edges = edges
+            .iter()
+            .map(|edge_loop| edge_loop.len() as u32)
+            .sum::<u32>();
+        self.cells.len() as u32 * n_edges
+    }
+
+    fn exterior_segments(&self) -> HashSet<Segment> {
+        fn get_edges(point: Point) -> [Segment; 4] {
+            [
+                Segment {
+                    top_left: point,
+                    orientation: Orientation2D::Vertical,
+                },
+                Segment {
+                    top_left: Point::new(point.x + 1, point.y),
+                    orientation: Orientation2D::Vertical,
+                },
+                Segment {
+                    top_left: Point::new(point.x, point.y + 1),
+                    orientation: Orientation2D::Horizontal,
+                },
+                Segment {
+                    top_left: point,
+                    orientation: Orientation2D::Horizontal,
+                },
+            ]
+        }
+
+        let mut edges = HashSet::new();
+        for point in self.cells.iter() {
+            for edge in get_edges(*point) {
+                if !edges.insert(edge) {
+                    // If the edge is already in the set, it's an interior edge and should be removed.
+                    edges.remove(&edge);
+                }
+            }
+        }
+        edges
+    }
+
+    // Each element is a connected path of edges.
+    //
+    // There may be multiple paths if the region has holes.
+    fn get_edges(&self) -> Vec<Vec<Edge>> {
+        let exterior = {
+            let mut exterior = self.exterior_segments().into_iter().collect::<Vec<_>>();
+            // Sorting by row first means each loop starts from its top row.
+            exterior.sort_by_key(|segment| {
+                (segment.top_left.y, segment.top_left.x, segment.orientation)
+            });
+            exterior
+        };
+        let mut edge_loops = Vec::new();
+        let mut seen = HashSet::<Segment>::new();
+
+        for segment in &exterior {
+            if seen.contains(segment) {
+                // Already visited.
+                continue;
+            }
+            seen.insert(*segment);
+
+            // Start a new loop.  We are guaranteed to be at a top left corner
+            // and be able to head down, with the region on our left.
+            debug_assert!(
+                segment.orientation == Orientation2D::Vertical,
+                "Unexpected segment orientation: {:?}",
+                segment
+            );
+            let start_point = segment.top_left;
+            let mut current_directed_segment = DirectedSegment {
+                start: segment.top_left,
+                direction: Direction4::Down,
+            };
+            let mut edge_loop = Vec::new();
+            let mut current_edge = Edge {
+                top_left: segment.top_left,
+                orientation: segment.orientation,
+                length: 1,
+            };
+
+            // Continue moving around the outside until we reach the start point.
+            while current_directed_segment.end() != start_point {
+                // We start by trying to turn left, towards the region.  Doing so rather
+                // than first trying to go straight is important to avoid handling
+                // 'mobius-strip' cases, as shown in the day 12 mobius fixture.
+                let mut next_directed_segment = current_directed_segment
+                    .extend_in_current_direction()
+                    .turn_left();
+                let initial_direction = next_directed_segment.direction;
+                // The direction we just came from.
+                let reverse_direction = current_directed_segment.direction.reverse();
+                while next_directed_segment.direction == reverse_direction
+                    || !exterior.contains(&next_directed_segment.to_segment())
+                {
+                    next_directed_segment = next_directed_segment.turn_left();
+                    if next_directed_segment.direction == initial_direction {
+                        // We rotated all the way around and didn't find a new edge.
+                        panic!("No next edge found from {:?}", current_directed_segment);
+                    }
+                }
+
+                if next_directed_segment.direction == current_directed_segment.direction {
+                    // Continuing in the same direction.  Extend the current edge.
+                    current_edge.length += 1;
+                    current_edge.top_left = current_edge
+                        .top_left
+                        .min(next_directed_segment.to_segment().top_left);
+                } else {
+                    // We hit a corner.  Finish the current edge.
+                    edge_loop.push(current_edge);
+
+                    // Start a new edge in the new direction.
+                    let next_segment = next_directed_segment.to_segment();
+                    current_edge = Edge {
+                        top_left: next_segment.top_left,
+                        orientation: next_segment.orientation,
+                        length: 1,
+                    };
+                }
+                current_directed_segment = next_directed_segment;
+                seen.insert(current_directed_segment.to_segment());
+            }
+            // We've completed the loop.  Flush the last edge.
+            edge_loop.push(current_edge);
+            edge_loops.push(edge_loop);
+        }
+        edge_loops
+    }
+}
+
+pub fn find_regions(grid: &Grid<u8>) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut visited = HashSet::<Vec2<usize>>::new();
+    for (start, &plant_type) in grid.iter() {
+        if visited.contains(&start) {
+            continue;
+        }
+        let cells = search::reachable([start], |&point| {
+            grid.neighbours4(point)
+                .filter(move |&next| grid[next] == plant_type)
+        });
+        visited.extend(&cells);
+        regions.push(Region {
+            cells: cells
+                .into_iter()
+                .map(|point| {
+                    point
+                        .try_cast::<i16>()
+                        .expect("Grid too large for i16 coordinates")
+                })
+                .collect(),
+        });
+    }
+    regions
+}
+
+pub fn find_cost(grid: &Grid<u8>) -> u32 {
+    find_regions(grid).iter().map(|region| region.cost()).sum()
+}
+
+pub fn find_discount_cost(grid: &Grid<u8>) -> u32 {
+    find_regions(grid)
+        .iter()
+        .map(|region| region.discount_cost())
+        .sum()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Orientation2D {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Segment {
+    top_left: Point,
+    orientation: Orientation2D,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct DirectedSegment {
+    start: Point,
+    direction: Direction4,
+}
+
+impl DirectedSegment {
+    // Return the next segment in the current direction.
+    fn extend_in_current_direction(&self) -> Self {
+        DirectedSegment {
+            start: self.end(),
+            direction: self.direction,
+        }
+    }
+
+    fn turn_left(&self) -> Self {
+        DirectedSegment {
+            start: self.start,
+            direction: self.direction.turn_left(),
+        }
+    }
+
+    fn to_segment(self) -> Segment {
+        Segment {
+            top_left: self.start.min(self.end()),
+            orientation: match self.direction {
+                Direction4::Up | Direction4::Down => Orientation2D::Vertical,
+                Direction4::Left | Direction4::Right => Orientation2D::Horizontal,
+            },
+        }
+    }
+
+    fn end(&self) -> Point {
+        self.start + self.direction.unit_vector()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Edge {
+    top_left: Point,
+    orientation: Orientation2D,
+    length: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: Vec<&str>) -> Grid<u8> {
+        Grid::parse_ascii(&input.join("\n")).unwrap()
+    }
+
+    #[test]
+    fn test_find_regions() {
+        let test_input = vec!["AAAA", "BBCD", "BBCC", "EEEC"];
+        assert_eq!(find_regions(&grid(test_input)).len(), 5);
+    }
+
+    #[test]
+    fn test_find_regions_sizes() {
+        let test_input = vec!["AAAA", "BBCD", "BBCC", "EEEC"];
+        let regions = find_regions(&grid(test_input));
+        assert_eq!(
+            regions
+                .iter()
+                .map(|region| region.cells.len())
+                .collect::<Vec<_>>(),
+            vec![4, 4, 4, 1, 3]
+        );
+    }
+
+    #[test]
+    fn test_exterior_segments_single_cell() {
+        let region = Region {
+            cells: HashSet::from([Point::new(1, 1)]),
+        };
+        assert_eq!(
+            region.exterior_segments(),
+            HashSet::from([
+                Segment {
+                    top_left: Point::new(1, 1),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(2, 1),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(1, 1),
+                    orientation: Orientation2D::Horizontal
+                },
+                Segment {
+                    top_left: Point::new(1, 2),
+                    orientation: Orientation2D::Horizontal
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_exterior_segments_l_shape() {
+        let region = Region {
+            cells: HashSet::from([Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)]),
+        };
+        assert_eq!(
+            region.exterior_segments(),
+            HashSet::from([
+                Segment {
+                    top_left: Point::new(0, 0),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(0, 1),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(0, 2),
+                    orientation: Orientation2D::Horizontal
+                },
+                Segment {
+                    top_left: Point::new(1, 1),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(1, 1),
+                    orientation: Orientation2D::Horizontal
+                },
+                Segment {
+                    top_left: Point::new(2, 0),
+                    orientation: Orientation2D::Vertical
+                },
+                Segment {
+                    top_left: Point::new(1, 0),
+                    orientation: Orientation2D::Horizontal
+                },
+                Segment {
+                    top_left: Point::new(0, 0),
+                    orientation: Orientation2D::Horizontal
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_find_edges_single_square() {
+        let region = Region {
+            cells: HashSet::from([Point::new(1, 1)]),
+        };
+        let edges: Vec<HashSet<Edge>> = region
+            .get_edges()
+            .iter()
+            .map(|edge_loop| edge_loop.iter().copied().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        let expected = vec![HashSet::from([
+            Edge {
+                top_left: Point::new(1, 1),
+                orientation: Orientation2D::Vertical,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(1, 2),
+                orientation: Orientation2D::Horizontal,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(2, 1),
+                orientation: Orientation2D::Vertical,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(1, 1),
+                orientation: Orientation2D::Horizontal,
+                length: 1,
+            },
+        ])];
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn test_find_edges_l_shape() {
+        let region = Region {
+            cells: HashSet::from([Point::new(0, 0), Point::new(0, 1), Point::new(1, 0)]),
+        };
+        let edges: Vec<HashSet<Edge>> = region
+            .get_edges()
+            .iter()
+            .map(|edge_loop| edge_loop.iter().copied().collect::<HashSet<_>>())
+            .collect::<Vec<_>>();
+
+        let expected = vec![HashSet::from([
+            Edge {
+                top_left: Point::new(0, 0),
+                orientation: Orientation2D::Vertical,
+                length: 2,
+            },
+            Edge {
+                top_left: Point::new(0, 2),
+                orientation: Orientation2D::Horizontal,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(1, 1),
+                orientation: Orientation2D::Vertical,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(1, 1),
+                orientation: Orientation2D::Horizontal,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(2, 0),
+                orientation: Orientation2D::Vertical,
+                length: 1,
+            },
+            Edge {
+                top_left: Point::new(0, 0),
+                orientation: Orientation2D::Horizontal,
+                length: 2,
+            },
+        ])];
+        assert_eq!(edges, expected);
+    }
+}
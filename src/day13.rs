@@ -0,0 +1,425 @@
+// Day 13: Claw Contraption
+//
+// These are linear equations, so there is only one solution, and we don't need to
+// find the best solution, just if there is an integer solution.  The exception is
+// when the buttons move the claw in the same direction, which the puzzle's inputs
+// avoid, but then the cheapest of the solutions along that line is found.
+
+use std::cmp::Ordering;
+
+use crate::number_theory::{extended_gcd, solve_2x2};
+use crate::parser::{integer, key_value, literal, pair, parse_all, preceded, sections, Parser};
+use crate::{Answer, Param, Params, Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Claw Contraption";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "prize_offset",
+        default: 10000000000000,
+        min: 0,
+        max: 1_000_000_000_000_000,
+        description: "Amount added to both prize coordinates in part 2",
+    }];
+    type Input = Vec<ClawMachine>;
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        Ok(parse_all(&grammar(), input)?)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        input.iter().filter_map(|m| m.cost()).sum::<i64>().into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        let prize_offset = params.get::<i64>("prize_offset");
+        input
+            .iter()
+            .filter_map(|m| m.with_prize_offset(prize_offset).cost())
+            .sum::<i64>()
+            .into()
+    }
+
+    // The reference can only check machines like the puzzle's.
+    fn reference_part1(input: &Self::Input, _params: &Params) -> Option<Answer> {
+        input.iter().all(ClawMachine::moves_forwards).then(|| {
+            input
+                .iter()
+                .filter_map(|m| m.reference_cost())
+                .sum::<i64>()
+                .into()
+        })
+    }
+
+    fn reference_part2(input: &Self::Input, params: &Params) -> Option<Answer> {
+        let prize_offset = params.get::<i64>("prize_offset");
+        // The search takes as many steps as the prize is far away.
+        let feasible =
+            prize_offset <= MAX_REFERENCE_OFFSET && input.iter().all(ClawMachine::moves_forwards);
+        feasible.then(|| {
+            input
+                .iter()
+                .filter_map(|m| m.with_prize_offset(prize_offset).reference_cost())
+                .sum::<i64>()
+                .into()
+        })
+    }
+}
+
+/// The largest prize offset the reference solution is used with.
+pub const MAX_REFERENCE_OFFSET: i64 = 1_000_000;
+
+/// The movement of each button, and where the prize is.
+#[derive(Debug)]
+pub struct ClawMachine {
+    pub a_x: i64,
+    pub a_y: i64,
+    pub b_x: i64,
+    pub b_y: i64,
+    pub prize_x: i64,
+    pub prize_y: i64,
+}
+
+/// How many times each button is pressed.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ClawSolution {
+    pub n_a: i64,
+    pub n_b: i64,
+}
+
+impl ClawMachine {
+    pub fn solve(&self) -> Option<ClawSolution> {
+        let [n_a, n_b] = solve_2x2(
+            [[self.a_x, self.b_x], [self.a_y, self.b_y]],
+            [self.prize_x, self.prize_y],
+        )?;
+        // The buttons can't be pressed a negative number of times.
+        (n_a >= 0 && n_b >= 0).then_some(ClawSolution { n_a, n_b })
+    }
+
+    pub fn cost(&self) -> Option<i64> {
+        let det = i128::from(self.a_x) * i128::from(self.b_y)
+            - i128::from(self.a_y) * i128::from(self.b_x);
+        if det == 0 {
+            return self.cheapest_with_parallel_buttons();
+        }
+        self.solve().map(|solution| 3 * solution.n_a + solution.n_b)
+    }
+
+    // When the buttons move the claw in the same direction, there may be many ways to
+    // win.  Along an axis that the buttons move on, they are the solutions of
+    // `a * n_a + b * n_b = prize`, which step together from one solution, and the cost
+    // changes steadily with each step, so the cheapest is at one end of those with no
+    // negative presses.
+    fn cheapest_with_parallel_buttons(&self) -> Option<i64> {
+        let (a, b, prize) = if (self.a_x, self.b_x) != (0, 0) {
+            (self.a_x, self.b_x, self.prize_x)
+        } else if (self.a_y, self.b_y) != (0, 0) {
+            (self.a_y, self.b_y, self.prize_y)
+        } else {
+            // Neither button moves the claw.
+            return ((self.prize_x, self.prize_y) == (0, 0)).then_some(0);
+        };
+        let (g, x, y) = extended_gcd(a, b);
+        if prize % g != 0 {
+            return None;
+        }
+        let (g, scale) = (i128::from(g), i128::from(prize / g));
+        // Each step adds `step_a` presses of A and `step_b` of B.
+        let (n_a, n_b) = (i128::from(x) * scale, i128::from(y) * scale);
+        let (step_a, step_b) = (i128::from(b) / g, -i128::from(a) / g);
+
+        // The range of steps that keeps both numbers of presses non-negative.
+        let (mut lowest, mut highest) = (None::<i128>, None::<i128>);
+        for (presses, step) in [(n_a, step_a), (n_b, step_b)] {
+            match step.cmp(&0) {
+                Ordering::Greater => {
+                    let bound = -presses.div_euclid(step);
+                    lowest = Some(lowest.map_or(bound, |lowest| lowest.max(bound)));
+                }
+                Ordering::Less => {
+                    let bound = presses.div_euclid(-step);
+                    highest = Some(highest.map_or(bound, |highest| highest.min(bound)));
+                }
+                Ordering::Equal if presses < 0 => return None,
+                Ordering::Equal => {}
+            }
+        }
+        if let (Some(lowest), Some(highest)) = (lowest, highest) {
+            if lowest > highest {
+                return None;
+            }
+        }
+        // Presses cost nothing less than zero, so the range is bounded on the side
+        // that the cost falls towards.
+        let steps = match (3 * step_a + step_b).cmp(&0) {
+            Ordering::Greater => lowest?,
+            Ordering::Less => highest?,
+            Ordering::Equal => lowest.or(highest)?,
+        };
+        let (n_a, n_b) = (n_a + steps * step_a, n_b + steps * step_b);
+
+        // The prize may still be off the line the buttons move along.
+        let reaches = |a: i64, b: i64, prize: i64| {
+            n_a * i128::from(a) + n_b * i128::from(b) == i128::from(prize)
+        };
+        if !reaches(self.a_x, self.b_x, self.prize_x) || !reaches(self.a_y, self.b_y, self.prize_y)
+        {
+            return None;
+        }
+        i64::try_from(3 * n_a + n_b).ok()
+    }
+
+    /// The cheapest way to win the prize, found by trying every number of presses of A.
+    ///
+    /// This checks `cost`, including when the buttons move the claw in the same direction
+    /// and there are several ways to win, without solving any equations.  The buttons must both
+    /// move the claw right and down, as they do in the puzzle; if not, this gives None.
+    pub fn reference_cost(&self) -> Option<i64> {
+        if !self.moves_forwards() {
+            return None;
+        }
+        let max_a = (self.prize_x / self.a_x).min(self.prize_y / self.a_y);
+        (0..=max_a)
+            .filter_map(|n_a| {
+                let (rest_x, rest_y) =
+                    (self.prize_x - n_a * self.a_x, self.prize_y - n_a * self.a_y);
+                let n_b = rest_x / self.b_x;
+                (rest_x % self.b_x == 0 && n_b * self.b_y == rest_y).then_some(3 * n_a + n_b)
+            })
+            .min()
+    }
+
+    /// Whether both buttons move the claw right and down.
+    pub fn moves_forwards(&self) -> bool {
+        [self.a_x, self.a_y, self.b_x, self.b_y]
+            .iter()
+            .all(|&step| step > 0)
+    }
+
+    pub fn with_prize_offset(&self, offset: i64) -> Self {
+        Self {
+            prize_x: self.prize_x + offset,
+            prize_y: self.prize_y + offset,
+            ..*self
+        }
+    }
+}
+
+// Each machine is a section like:
+//   Button A: X+94, Y+34
+//   Button B: X+22, Y+67
+//   Prize: X=8400, Y=5400
+fn grammar() -> impl Parser<Output = Vec<ClawMachine>> {
+    let coordinates = |separator| {
+        pair(
+            key_value("X", separator, integer::<i64>()),
+            ", ",
+            key_value("Y", separator, integer::<i64>()),
+        )
+    };
+    let machine = preceded("Button A: ", coordinates("+"))
+        .skip(literal("\n"))
+        .then(preceded("Button B: ", coordinates("+")))
+        .skip(literal("\n"))
+        .then(preceded("Prize: ", coordinates("=")))
+        .map(
+            |(((a_x, a_y), (b_x, b_y)), (prize_x, prize_y))| ClawMachine {
+                a_x,
+                a_y,
+                b_x,
+                b_y,
+                prize_x,
+                prize_y,
+            },
+        );
+    sections(machine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Registered, Runnable};
+    use crate::Overrides;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_solve_good_example() {
+        let machine = ClawMachine {
+            a_x: 94,
+            a_y: 34,
+            b_x: 22,
+            b_y: 67,
+            prize_x: 8400,
+            prize_y: 5400,
+        };
+        assert_eq!(machine.solve(), Some(ClawSolution { n_a: 80, n_b: 40 }));
+    }
+
+    #[test]
+    fn test_solve_bad_example() {
+        let machine = ClawMachine {
+            a_x: 26,
+            a_y: 66,
+            b_x: 67,
+            b_y: 21,
+            prize_x: 12748,
+            prize_y: 12176,
+        };
+        assert_eq!(machine.solve(), None);
+    }
+
+    #[test]
+    fn test_cost_good_example() {
+        let machine = ClawMachine {
+            a_x: 94,
+            a_y: 34,
+            b_x: 22,
+            b_y: 67,
+            prize_x: 8400,
+            prize_y: 5400,
+        };
+        assert_eq!(machine.cost(), Some(280));
+    }
+
+    #[test]
+    fn test_cost_bad_example() {
+        let machine = ClawMachine {
+            a_x: 26,
+            a_y: 66,
+            b_x: 67,
+            b_y: 21,
+            prize_x: 12748,
+            prize_y: 12176,
+        };
+        assert_eq!(machine.cost(), None);
+    }
+
+    #[test]
+    fn reference_finds_cheapest_of_many_solutions() {
+        // Both buttons move diagonally, so any mix of 10 presses wins.
+        let machine = ClawMachine {
+            a_x: 2,
+            a_y: 2,
+            b_x: 1,
+            b_y: 1,
+            prize_x: 10,
+            prize_y: 10,
+        };
+        assert_eq!(machine.cost(), Some(10));
+        assert_eq!(machine.reference_cost(), Some(10));
+    }
+
+    #[test]
+    fn cost_handles_parallel_buttons() {
+        let machine = |a_x, a_y, b_x, b_y, prize_x, prize_y| ClawMachine {
+            a_x,
+            a_y,
+            b_x,
+            b_y,
+            prize_x,
+            prize_y,
+        };
+        // Pressing A three times or B nine times both cost 9, as does any mix.
+        assert_eq!(machine(3, 3, 1, 1, 9, 9).cost(), Some(9));
+        // A moves far enough to be worth its cost.
+        assert_eq!(machine(5, 10, 1, 2, 12, 24).cost(), Some(8));
+        // Only some mixes land on the prize: 5 presses of A, or 2 of each.
+        assert_eq!(machine(4, 4, 6, 6, 20, 20).cost(), Some(8));
+        assert_eq!(machine(4, 4, 6, 6, 7, 7).cost(), None);
+        // The prize is off the line.
+        assert_eq!(machine(1, 1, 2, 2, 4, 5).cost(), None);
+        // A button that doesn't move is never worth pressing.
+        assert_eq!(machine(0, 0, 2, 3, 4, 6).cost(), Some(2));
+        assert_eq!(machine(0, 0, 0, 0, 0, 0).cost(), Some(0));
+        assert_eq!(machine(0, 0, 0, 0, 1, 0).cost(), None);
+        // Buttons moving in opposite directions can still reach the prize cheaply.
+        assert_eq!(machine(2, 2, -3, -3, 1, 1).cost(), Some(7));
+    }
+
+    #[test]
+    fn negative_presses_do_not_win() {
+        let machine = ClawMachine {
+            a_x: 1,
+            a_y: 2,
+            b_x: 3,
+            b_y: 1,
+            prize_x: 1,
+            prize_y: 7,
+        };
+        assert_eq!(machine.solve(), None);
+        assert_eq!(machine.reference_cost(), None);
+    }
+
+    #[test]
+    fn reference_needs_buttons_that_move_forwards() {
+        let mut machines = Day13::parse(
+            "Button A: X+0, Y+5\nButton B: X+3, Y+1\nPrize: X=6, Y=12\n",
+            &Params::default(),
+        )
+        .unwrap();
+        assert_eq!(Day13::reference_part1(&machines, &Params::default()), None);
+        let machine = machines.remove(0);
+        assert_eq!(machine.cost(), Some(8));
+        assert_eq!(machine.reference_cost(), None);
+        let backwards = ClawMachine { b_y: -1, ..machine };
+        assert_eq!(backwards.reference_cost(), None);
+    }
+
+    // Buttons that move the claw right and down, half of them in the same direction,
+    // which is rare when chosen at random.
+    fn buttons() -> impl Strategy<Value = [i64; 4]> {
+        let parallel = (1_i64..20, 1_i64..20, 1_i64..6, 1_i64..6)
+            .prop_map(|(x, y, a, b)| [a * x, a * y, b * x, b * y]);
+        prop_oneof![prop::array::uniform4(1_i64..100), parallel]
+    }
+
+    fn format_machine([a_x, a_y, b_x, b_y]: [i64; 4], prize_x: i64, prize_y: i64) -> String {
+        format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a_x, a_y, b_x, b_y, prize_x, prize_y
+        )
+    }
+
+    fn cross_check(machines: &[String], prize_offset: i64) -> Vec<crate::runner::CrossCheck> {
+        let mut overrides = Overrides::default();
+        overrides.insert("prize_offset", prize_offset);
+        Registered::<Day13>::new()
+            .cross_check(&machines.join("\n"), &overrides)
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_reference_on_winnable_machines(
+            machines in prop::collection::vec((buttons(), 0_i64..=100, 0_i64..=100), 1..5),
+            prize_offset in 0_i64..1000,
+        ) {
+            let machines = machines
+                .into_iter()
+                .map(|(buttons @ [a_x, a_y, b_x, b_y], n_a, n_b)| {
+                    format_machine(buttons, n_a * a_x + n_b * b_x, n_a * a_y + n_b * b_y)
+                })
+                .collect::<Vec<_>>();
+            for check in cross_check(&machines, prize_offset) {
+                prop_assert!(check.reference.is_some());
+                prop_assert!(!check.disagrees(), "{:?}", check);
+            }
+        }
+
+        #[test]
+        fn agrees_with_reference_on_any_prize(
+            machines in prop::collection::vec((buttons(), 0_i64..10_000, 0_i64..10_000), 1..5),
+        ) {
+            let machines = machines
+                .into_iter()
+                .map(|(buttons, prize_x, prize_y)| format_machine(buttons, prize_x, prize_y))
+                .collect::<Vec<_>>();
+            for check in cross_check(&machines, 0) {
+                prop_assert!(!check.disagrees(), "{:?}", check);
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+// Day 14:  Restroom Redoubt
+
+use anyhow::{anyhow, bail};
+use itertools::Itertools;
+
+use crate::number_theory::checked_lcm;
+use crate::par::prelude::*;
+use crate::parser::{integer, key_value, lines, literal, pair, parse_all, Parser};
+use crate::render::Palette;
+use crate::simulation::Simulation;
+use crate::{Answer, Grid, Param, Params, Solution, Vec2};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            min: 1,
+            max: i16::MAX as i64,
+            description: "Width of the room",
+        },
+        Param {
+            name: "height",
+            default: 103,
+            min: 1,
+            max: i16::MAX as i64,
+            description: "Height of the room",
+        },
+        Param {
+            name: "timesteps",
+            default: 100,
+            min: 0,
+            max: i32::MAX as i64,
+            description: "Seconds the robots move for in part 1",
+        },
+    ];
+    type Input = RobotGrid;
+
+    fn parse(input: &str, params: &Params) -> anyhow::Result<Self::Input> {
+        RobotGrid::new_from_str(input, params.get("width"), params.get("height"))
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        advance_and_multiply_quads(input, params.get("timesteps")).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        find_minimum_score(input).into()
+    }
+}
+
+/// The grid at the timestep where the quad score is minimized.
+///
+/// We hope that this shows a christmas tree, but it should be verified by eye.
+pub fn minimum_score_frame(grid: &RobotGrid) -> RobotGrid {
+    grid.advance_by(find_minimum_score(grid))
+}
+
+/// The colours of the room: robots are green on black.
+pub fn palette() -> Palette<bool> {
+    Palette::new([0, 0, 0], [(true, [40, 200, 80])])
+}
+
+/// The room after every `every` seconds, until the robots are back where they started.
+pub fn frames(grid: &RobotGrid, every: usize) -> impl Iterator<Item = Grid<bool>> + '_ {
+    (0..grid.period)
+        .step_by(every.max(1))
+        .map(|timestep| grid.advance_by(timestep).to_grid())
+}
+
+pub type Point = Vec2<i16>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl TryFrom<&str> for Robot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(parse_all(&robot(), value)?)
+    }
+}
+
+// A robot is like `p=0,4 v=3,-3`.
+fn robot() -> impl Parser<Output = Robot> {
+    let point = || pair(integer(), ",", integer()).map(|(x, y)| Point { x, y });
+    key_value("p", "=", point())
+        .skip(literal(" "))
+        .then(key_value("v", "=", point()))
+        .map(|(position, velocity)| Robot { position, velocity })
+}
+
+/// Robots moving on a grid that wraps around at its edges.
+#[derive(Clone)]
+pub struct RobotGrid {
+    robots: Vec<Robot>,
+    width: i16,
+    height: i16,
+    // The grid must repeat in X every width steps, and in Y every height steps, so it
+    // repeats entirely after their LCM.
+    period: i64,
+}
+
+impl RobotGrid {
+    /// Fails if a robot starts outside the room, or the room is too big to simulate.
+    pub fn new_from_str(value: &str, width: i16, height: i16) -> Result<Self, anyhow::Error> {
+        let robots = parse_all(&lines(robot()), value)?;
+        for (index, robot) in robots.iter().enumerate() {
+            let Vec2 { x, y } = robot.position;
+            if !(0..width).contains(&x) || !(0..height).contains(&y) {
+                bail!(
+                    "Robot {} at {},{} is outside the {}x{} room",
+                    index + 1,
+                    x,
+                    y,
+                    width,
+                    height
+                );
+            }
+        }
+        let period = checked_lcm(i64::from(width), i64::from(height))
+            .ok_or_else(|| anyhow!("The period of the {}x{} room overflows", width, height))?;
+        Ok(Self {
+            robots,
+            width,
+            height,
+            period,
+        })
+    }
+
+    pub fn width(&self) -> i16 {
+        self.width
+    }
+
+    pub fn height(&self) -> i16 {
+        self.height
+    }
+
+    pub fn advance_by(&self, timesteps: i64) -> Self {
+        Self {
+            robots: self
+                .robots
+                .iter()
+                .map(|robot| {
+                    // Widen so that many timesteps don't overflow before wrapping.
+                    let moved =
+                        robot.position.cast::<i64>() + robot.velocity.cast::<i64>() * timesteps;
+                    Robot {
+                        position: Point::new(
+                            moved.x.rem_euclid(i64::from(self.width)) as i16,
+                            moved.y.rem_euclid(i64::from(self.height)) as i16,
+                        ),
+                        ..*robot
+                    }
+                })
+                .collect(),
+            width: self.width,
+            height: self.height,
+            period: self.period,
+        }
+    }
+
+    /// The room with true wherever at least one robot is.
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.width as usize, self.height as usize, false);
+        for robot in &self.robots {
+            grid[Vec2::new(robot.position.x as usize, robot.position.y as usize)] = true;
+        }
+        grid
+    }
+
+    /// Count the number of robots in each quadrant.
+    ///
+    /// Robots on the axis between quadrants are not counted.
+    pub fn count_quads(&self) -> [u16; 4] {
+        let mut quad_count = [0; 4];
+        let midpoint_x = self.width / 2;
+        let midpoint_y = self.height / 2;
+        for robot in &self.robots {
+            if robot.position.x < midpoint_x && robot.position.y < midpoint_y {
+                quad_count[0] += 1;
+            } else if robot.position.x > midpoint_x && robot.position.y < midpoint_y {
+                quad_count[1] += 1;
+            } else if robot.position.x < midpoint_x && robot.position.y > midpoint_y {
+                quad_count[2] += 1;
+            } else if robot.position.x > midpoint_x && robot.position.y > midpoint_y {
+                quad_count[3] += 1;
+            }
+        }
+        quad_count
+    }
+}
+
+impl std::fmt::Display for RobotGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut grid = vec![vec![' '; self.width as usize]; self.height as usize];
+        for robot in &self.robots {
+            grid[robot.position.y as usize][robot.position.x as usize] = '#';
+        }
+        for row in grid {
+            writeln!(f, "{}", row.iter().join(""))?;
+        }
+        Ok(())
+    }
+}
+
+/// Each step is a second.  The robots never stop, so this never finishes.
+impl Simulation for RobotGrid {
+    fn step(&mut self) -> bool {
+        *self = self.advance_by(1);
+        true
+    }
+
+    fn render(&self) -> String {
+        let [a, b, c, d] = self.count_quads();
+        format!("{}Robots in each quadrant: {} {} {} {}\n", self, a, b, c, d)
+    }
+}
+
+pub fn advance_and_multiply_quads(grid: &RobotGrid, timesteps: i64) -> u32 {
+    grid.advance_by(timesteps)
+        .count_quads()
+        .iter()
+        .map(|&x| x as u32)
+        .product()
+}
+
+// Returns the timestep at which the score is minimized.
+//
+// A room that repeats every step only has the start, so that is the answer.
+pub fn find_minimum_score(grid: &RobotGrid) -> i64 {
+    (1..grid.period)
+        .into_par_iter()
+        .map(|timestep| (timestep, advance_and_multiply_quads(grid, timestep)))
+        .min_by_key(|(_, score)| *score)
+        .map_or(0, |(timestep, _)| timestep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claim::assert_ok_eq;
+
+    const TEST_GRID: &str = r"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    #[test]
+    fn test_parse_robot() {
+        assert_ok_eq!(
+            Robot::try_from("p=89,76 v=-46,-5"),
+            Robot {
+                position: Point { x: 89, y: 76 },
+                velocity: Point { x: -46, y: -5 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_count_quads() {
+        let grid = RobotGrid::new_from_str(TEST_GRID, 11, 7).expect("failed to parse grid");
+        assert_eq!(grid.count_quads(), [4, 0, 2, 2]);
+    }
+
+    #[test]
+    fn steps_are_seconds() {
+        let mut grid =
+            RobotGrid::new_from_str("p=2,4 v=2,-3", 11, 7).expect("failed to parse grid");
+        for _ in 0..5 {
+            assert!(grid.step());
+        }
+        assert_eq!(grid.robots[0].position, Point::new(1, 3));
+        // On the middle row, so in no quadrant.
+        assert!(grid.render().contains("Robots in each quadrant: 0 0 0 0"));
+    }
+
+    #[test]
+    fn frames_show_the_robots() {
+        let grid = RobotGrid::new_from_str(TEST_GRID, 11, 7).expect("failed to parse grid");
+        let first = frames(&grid, 1).next().unwrap();
+        assert!(first[Vec2::new(0, 4)]);
+        assert!(!first[Vec2::new(1, 4)]);
+        assert_eq!(first.iter().filter(|(_, &robot)| robot).count(), 11);
+        assert_eq!(frames(&grid, 10).count(), 8);
+    }
+
+    #[test]
+    fn minimum_score_frame_has_the_lowest_score() {
+        let grid = RobotGrid::new_from_str(TEST_GRID, 11, 7).expect("failed to parse grid");
+        let score = advance_and_multiply_quads(&minimum_score_frame(&grid), 0);
+        assert!((1..grid.period).all(|t| advance_and_multiply_quads(&grid, t) >= score));
+        assert_eq!(
+            score,
+            advance_and_multiply_quads(&grid, find_minimum_score(&grid))
+        );
+    }
+
+    #[test]
+    fn robots_must_start_in_the_room() {
+        let error = RobotGrid::new_from_str(TEST_GRID, 10, 7).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Robot 3 at 10,3 is outside the 10x7 room"
+        );
+        assert!(RobotGrid::new_from_str("p=-1,0 v=1,1", 11, 7).is_err());
+    }
+
+    #[test]
+    fn rooms_of_any_size_repeat() {
+        let grid = RobotGrid::new_from_str("p=0,0 v=1,1", 1, 1).expect("failed to parse grid");
+        assert_eq!(find_minimum_score(&grid), 0);
+        assert_eq!(frames(&grid, 1).count(), 1);
+
+        let grid = RobotGrid::new_from_str("p=0,0 v=1,1", 200, 201).expect("failed to parse grid");
+        assert_eq!(grid.period, 40_200);
+        assert_eq!(grid.advance_by(40_200).robots, grid.robots);
+    }
+}
@@ -0,0 +1,441 @@
+// Day 15: Warehouse Woes
+
+use crate::charmap::{Glyph, Legend};
+use crate::parser::{block, char_where, lines, many1, parse_all, section_break, Parser};
+use crate::render::Palette;
+use crate::simulation::Simulation;
+use crate::{Answer, Direction4, Grid, Params, Solution, Vec2};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+    type Input = (Warehouse, Vec<Direction4>);
+
+    fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        let (warehouse, moves) = input;
+        apply_moves(warehouse, moves).gps().into()
+    }
+
+    fn part2(_input: &Self::Input, _params: &Params) -> Answer {
+        // Not solved yet.
+        Answer::Unsolved
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Items {
+    Empty,
+    Box,
+    Wall,
+}
+
+// x is the column, with 0 at the left, and y is the row, with 0 at the top.
+pub type Position = Vec2<usize>;
+
+/// The map of walls and boxes, and where the robot is.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warehouse {
+    grid: Grid<Items>,
+    robot: Position,
+}
+
+// The robot's marker in the map; it stands on an empty floor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Robot;
+
+impl TryFrom<&str> for Warehouse {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let legend = Legend::new([
+            ('.', Glyph::Cell(Items::Empty)),
+            ('#', Glyph::Cell(Items::Wall)),
+            ('O', Glyph::Cell(Items::Box)),
+            ('@', Glyph::Entity(Robot, Items::Empty)),
+        ]);
+        let map = legend.parse(value)?;
+        let robot = match map.positions(&Robot).collect::<Vec<_>>()[..] {
+            [robot] => robot,
+            [] => anyhow::bail!("No robot found"),
+            _ => anyhow::bail!("More than one robot found"),
+        };
+        Ok(Self {
+            grid: map.grid,
+            robot,
+        })
+    }
+}
+
+impl Warehouse {
+    pub fn move_robot(&mut self, direction: Direction4) {
+        let Some(position_after_first_move) = self.move_in_direction(self.robot, direction) else {
+            return;
+        };
+        let mut curr_position = position_after_first_move;
+        loop {
+            match self[curr_position] {
+                Items::Empty => {
+                    // We can move.  Everything between the position after the first move and
+                    // the current position is something we can push.
+                    self.swap(position_after_first_move, curr_position);
+                    self.robot = position_after_first_move;
+                    break;
+                }
+                Items::Wall => {
+                    // Robot tried to move either itself or a string of boxes into a wall, denied.
+                    break;
+                }
+                Items::Box => {
+                    // A box, keep going.
+                    match self.move_in_direction(curr_position, direction) {
+                        Some(next_position) => curr_position = next_position,
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+
+    // The next position in the direction, or None at the edge of the map, which stops
+    // the robot and boxes as a wall would.
+    fn move_in_direction(&self, position: Position, direction: Direction4) -> Option<Position> {
+        self.grid.offset(position, direction.unit_vector())
+    }
+
+    fn swap(&mut self, position1: Position, position2: Position) {
+        let t = self.grid[position1];
+        self.grid[position1] = self.grid[position2];
+        self.grid[position2] = t;
+    }
+
+    pub fn grid(&self) -> &Grid<Items> {
+        &self.grid
+    }
+
+    pub fn robot(&self) -> Position {
+        self.robot
+    }
+
+    /// The warehouse with the robot drawn in.
+    pub fn to_grid(&self) -> Grid<WarehouseCell> {
+        let mut grid = self.grid.map(|item| match item {
+            Items::Empty => WarehouseCell::Floor,
+            Items::Box => WarehouseCell::Box,
+            Items::Wall => WarehouseCell::Wall,
+        });
+        grid[self.robot] = WarehouseCell::Robot;
+        grid
+    }
+
+    pub fn gps(&self) -> u32 {
+        self.grid
+            .iter()
+            .filter(|(_, &item)| item == Items::Box)
+            .map(|(position, _)| (position.y as u32) * 100 + (position.x as u32))
+            .sum()
+    }
+}
+
+impl std::ops::Index<Position> for Warehouse {
+    type Output = Items;
+
+    fn index(&self, index: Position) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+pub fn apply_moves(warehouse: &Warehouse, moves: &[Direction4]) -> Warehouse {
+    let mut warehouse = warehouse.clone();
+    for &m in moves {
+        warehouse.move_robot(m);
+    }
+    warehouse
+}
+
+/// Writes the map as in the puzzle.
+impl std::fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = self.to_grid().map(|cell| match cell {
+            WarehouseCell::Floor => '.',
+            WarehouseCell::Box => 'O',
+            WarehouseCell::Wall => '#',
+            WarehouseCell::Robot => '@',
+        });
+        write!(f, "{}", text)
+    }
+}
+
+/// The robot making its moves one at a time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobotMoves {
+    warehouse: Warehouse,
+    moves: Vec<Direction4>,
+    n_made: usize,
+}
+
+impl RobotMoves {
+    pub fn new(warehouse: &Warehouse, moves: &[Direction4]) -> Self {
+        Self {
+            warehouse: warehouse.clone(),
+            moves: moves.to_vec(),
+            n_made: 0,
+        }
+    }
+
+    pub fn warehouse(&self) -> &Warehouse {
+        &self.warehouse
+    }
+}
+
+impl Simulation for RobotMoves {
+    fn step(&mut self) -> bool {
+        let Some(&direction) = self.moves.get(self.n_made) else {
+            return false;
+        };
+        self.warehouse.move_robot(direction);
+        self.n_made += 1;
+        true
+    }
+
+    fn render(&self) -> String {
+        let next = self
+            .moves
+            .get(self.n_made)
+            .map_or("none".to_string(), |direction| {
+                direction.arrow().to_string()
+            });
+        format!(
+            "{}Move {} of {}, next {}; GPS {}\n",
+            self.warehouse,
+            self.n_made,
+            self.moves.len(),
+            next,
+            self.warehouse.gps()
+        )
+    }
+}
+
+/// What a square of the warehouse shows in a picture.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WarehouseCell {
+    Floor,
+    Box,
+    Wall,
+    Robot,
+}
+
+/// The colours of the warehouse: brown boxes and grey walls on a dark floor, with the
+/// robot in yellow.
+pub fn palette() -> Palette<WarehouseCell> {
+    Palette::new(
+        [24, 24, 24],
+        [
+            (WarehouseCell::Box, [170, 110, 50]),
+            (WarehouseCell::Wall, [110, 110, 120]),
+            (WarehouseCell::Robot, [250, 220, 40]),
+        ],
+    )
+}
+
+/// The warehouse before any moves, after every `every` moves, and after the last one.
+pub fn move_frames<'a>(
+    warehouse: &Warehouse,
+    moves: &'a [Direction4],
+    every: usize,
+) -> impl Iterator<Item = Grid<WarehouseCell>> + 'a {
+    let mut warehouse = warehouse.clone();
+    std::iter::once(warehouse.to_grid()).chain(moves.chunks(every.max(1)).map(move |chunk| {
+        for &m in chunk {
+            warehouse.move_robot(m);
+        }
+        warehouse.to_grid()
+    }))
+}
+
+fn parse_input(input: &str) -> Result<(Warehouse, Vec<Direction4>), anyhow::Error> {
+    Ok(parse_all(&grammar(), input)?)
+}
+
+// The map of the warehouse, a blank line, and then the moves.
+fn grammar() -> impl Parser<Output = (Warehouse, Vec<Direction4>)> {
+    block()
+        .try_map(|map| Warehouse::try_from(map.as_str()))
+        .skip(section_break())
+        .then(moves())
+}
+
+// The moves, with newlines inserted for readability.
+fn moves() -> impl Parser<Output = Vec<Direction4>> {
+    lines(many1(char_where("a move", Direction4::from_arrow))).map(|lines| lines.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = r"########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
+    #[test]
+    fn test_parse_moves() {
+        assert_eq!(
+            parse_all(&moves(), "^>v<\n<").unwrap(),
+            vec![
+                Direction4::Up,
+                Direction4::Right,
+                Direction4::Down,
+                Direction4::Left,
+                Direction4::Left
+            ]
+        );
+        let err = parse_all(&moves(), "^>\n<x").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn parse_reports_position_of_bad_glyph() {
+        let err = Warehouse::try_from("#@.\n#.x")
+            .unwrap_err()
+            .downcast::<crate::ParseError>()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(Warehouse::try_from("#..\n#..").is_err());
+    }
+
+    #[test]
+    fn move_into_wall() {
+        let warehouse = Warehouse::try_from("##@..#").expect("Failed to parse test input");
+        assert_eq!(warehouse.robot, Position::new(2, 0));
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Left]);
+        // Position should not change since we tried to push into a wall.
+        assert_eq!(warehouse_after_move, warehouse);
+    }
+
+    #[test]
+    fn edges_of_the_map_stop_moves_like_walls() {
+        for (map, direction) in [
+            (".@.", Direction4::Left),
+            (".@.", Direction4::Right),
+            ("@O", Direction4::Right),
+            ("@", Direction4::Up),
+        ] {
+            let warehouse = Warehouse::try_from(map).expect("Failed to parse test input");
+            let moves = [direction, direction, direction];
+            let warehouse_after_moves = apply_moves(&warehouse, &moves);
+            assert_eq!(warehouse_after_moves.grid, warehouse.grid, "{}", map);
+        }
+        let (warehouse, moves) = parse_input(".@.\n\n<<<").expect("Failed to parse input");
+        assert_eq!(apply_moves(&warehouse, &moves).robot, Position::new(0, 0));
+    }
+
+    #[test]
+    fn there_is_exactly_one_robot() {
+        let err = Warehouse::try_from("#@.@#").unwrap_err();
+        assert_eq!(err.to_string(), "More than one robot found");
+        assert!(Warehouse::try_from("#...#").is_err());
+    }
+
+    #[test]
+    fn move_into_space() {
+        let warehouse = Warehouse::try_from("##@..#").expect("Failed to parse test input");
+        assert_eq!(warehouse.robot, Position::new(2, 0));
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Right]);
+        assert_eq!(
+            warehouse_after_move,
+            Warehouse::try_from("##.@.#").expect("Failed to parse expected output")
+        );
+    }
+
+    #[test]
+    fn move_box_into_space() {
+        let warehouse = Warehouse::try_from("#.O@.#").expect("Failed to parse test input");
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Left]);
+        assert_eq!(
+            warehouse_after_move,
+            Warehouse::try_from("#O@..#").expect("Failed to parse expected output")
+        );
+    }
+
+    #[test]
+    fn move_two_boxes_into_space() {
+        let warehouse = Warehouse::try_from("#.#.OO@.#").expect("Failed to parse test input");
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Left]);
+        assert_eq!(
+            warehouse_after_move,
+            Warehouse::try_from("#.#OO@..#").expect("Failed to parse expected output")
+        );
+    }
+
+    #[test]
+    fn move_many_boxes_into_space() {
+        let warehouse = Warehouse::try_from("#.#@OOOOOO..#.#").expect("Failed to parse test input");
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Right]);
+        assert_eq!(
+            warehouse_after_move,
+            Warehouse::try_from("#.#.@OOOOOO.#.#").expect("Failed to parse expected output")
+        );
+    }
+
+    #[test]
+    fn move_box_into_wall() {
+        let warehouse = Warehouse::try_from("#.#OO@.#").expect("Failed to parse test input");
+
+        let warehouse_after_move = apply_moves(&warehouse, &[Direction4::Left]);
+        assert_eq!(warehouse_after_move, warehouse);
+    }
+
+    #[test]
+    fn multiple_moves_small_example() {
+        let (warehouse, moves) = parse_input(SMALL_EXAMPLE).expect("Failed to parse input");
+        assert_eq!(moves.len(), 15);
+
+        let expected = Warehouse::try_from(
+            "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########",
+        )
+        .expect("Failed to parse expected output");
+        assert_eq!(apply_moves(&warehouse, &moves), expected);
+    }
+
+    #[test]
+    fn simulation_makes_every_move() {
+        let (warehouse, moves) = parse_input(SMALL_EXAMPLE).expect("Failed to parse input");
+        let mut simulation = RobotMoves::new(&warehouse, &moves);
+        assert!(simulation
+            .render()
+            .starts_with(SMALL_EXAMPLE.split("\n\n").next().unwrap()));
+        while simulation.step() {}
+        assert_eq!(*simulation.warehouse(), apply_moves(&warehouse, &moves));
+        assert!(simulation
+            .render()
+            .ends_with("Move 15 of 15, next none; GPS 2028\n"));
+    }
+
+    #[test]
+    fn frames_end_with_the_last_move() {
+        let (warehouse, moves) = parse_input(SMALL_EXAMPLE).expect("Failed to parse input");
+        let frames = move_frames(&warehouse, &moves, 4).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[0], warehouse.to_grid());
+        assert_eq!(frames[0][Position::new(2, 2)], WarehouseCell::Robot);
+        assert_eq!(frames[4], apply_moves(&warehouse, &moves).to_grid());
+    }
+}
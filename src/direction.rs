@@ -0,0 +1,202 @@
+//! Compass directions on a grid, with y increasing downwards.
+
+use std::ops::Neg;
+
+use crate::vec2::{Coordinate, Vec2};
+
+/// The four orthogonal directions, listed clockwise from up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The displacement of one step in this direction.
+    pub fn unit_vector<T: Coordinate + Neg<Output = T>>(self) -> Vec2<T> {
+        match self {
+            Self::Up => Vec2::new(T::ZERO, -T::ONE),
+            Self::Right => Vec2::new(T::ONE, T::ZERO),
+            Self::Down => Vec2::new(T::ZERO, T::ONE),
+            Self::Left => Vec2::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    /// Parses one of `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::Up),
+            '>' => Some(Self::Right),
+            'v' => Some(Self::Down),
+            '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// Parses one of `N`, `E`, `S` or `W`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Self::Up),
+            'E' => Some(Self::Right),
+            'S' => Some(Self::Down),
+            'W' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow that `from_arrow` parses to this direction.
+    pub fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+}
+
+/// The orthogonal and diagonal directions, listed clockwise from up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The displacement of one step in this direction.  Diagonal steps move along both
+    /// axes at once.
+    pub fn unit_vector<T: Coordinate + Neg<Output = T>>(self) -> Vec2<T> {
+        let (vertical, horizontal) = match self {
+            Self::Up => (Some(Direction4::Up), None),
+            Self::UpRight => (Some(Direction4::Up), Some(Direction4::Right)),
+            Self::Right => (None, Some(Direction4::Right)),
+            Self::DownRight => (Some(Direction4::Down), Some(Direction4::Right)),
+            Self::Down => (Some(Direction4::Down), None),
+            Self::DownLeft => (Some(Direction4::Down), Some(Direction4::Left)),
+            Self::Left => (None, Some(Direction4::Left)),
+            Self::UpLeft => (Some(Direction4::Up), Some(Direction4::Left)),
+        };
+        [vertical, horizontal]
+            .into_iter()
+            .flatten()
+            .map(Direction4::unit_vector)
+            .sum()
+    }
+
+    /// Parses a compass point: `N`, `NE`, `E`, `SE`, `S`, `SW`, `W` or `NW`.
+    pub fn from_letters(s: &str) -> Option<Self> {
+        match s {
+            "N" => Some(Self::Up),
+            "NE" => Some(Self::UpRight),
+            "E" => Some(Self::Right),
+            "SE" => Some(Self::DownRight),
+            "S" => Some(Self::Down),
+            "SW" => Some(Self::DownLeft),
+            "W" => Some(Self::Left),
+            "NW" => Some(Self::UpLeft),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Left.reverse(), Direction4::Right);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.reverse(), Direction8::UpRight);
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+    }
+
+    #[test]
+    fn unit_vectors() {
+        assert_eq!(Direction4::Up.unit_vector::<i32>(), Vec2::new(0, -1));
+        assert_eq!(Direction8::DownLeft.unit_vector::<i8>(), Vec2::new(-1, 1));
+        for direction in Direction8::ALL {
+            assert_eq!(
+                direction.reverse().unit_vector::<i64>(),
+                -direction.unit_vector::<i64>()
+            );
+        }
+        assert_eq!(
+            Direction4::ALL
+                .map(|d| Direction8::from(d).unit_vector::<i16>())
+                .to_vec(),
+            Direction4::ALL.map(Direction4::unit_vector::<i16>).to_vec()
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let arrows = "^>v<".chars().map(Direction4::from_arrow);
+        let letters = "NESW".chars().map(Direction4::from_letter);
+        assert!(arrows.eq(letters));
+        assert_eq!(Direction4::from_arrow('x'), None);
+        assert_eq!(Direction4::Down.arrow(), 'v');
+        assert_eq!(Direction8::from_letters("SW"), Some(Direction8::DownLeft));
+        assert_eq!(Direction8::from_letters("NN"), None);
+    }
+}
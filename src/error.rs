@@ -0,0 +1,157 @@
+//! Errors produced while reading and parsing puzzle inputs.
+
+use std::fmt;
+
+/// Malformed puzzle input.
+///
+/// Lines and columns start from 1, and columns count characters rather than bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The day whose input was being parsed, if known.
+    pub day: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    /// The offending token.  Empty if the input ended too early.
+    pub token: String,
+    /// What was expected instead.
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error for the token starting at byte offset `start` of `line_text`.
+    pub fn at(
+        line: usize,
+        line_text: &str,
+        start: usize,
+        token: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            day: None,
+            line,
+            column: line_text[..start].chars().count() + 1,
+            token: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error for something missing from the end of `line_text`.
+    pub fn at_end(line: usize, line_text: &str, message: impl Into<String>) -> Self {
+        Self::at(line, line_text, line_text.len(), "", message)
+    }
+
+    /// Records the day whose input failed to parse.
+    pub fn with_day(mut self, day: &str) -> Self {
+        self.day = Some(day.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = &self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if self.token.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found `{}`", self.token)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Failure to read a puzzle input, either because the file could not be read or
+/// because its contents are malformed.
+#[derive(Debug)]
+pub enum InputError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl InputError {
+    /// Records the day whose input failed to parse.
+    pub fn with_day(self, day: &str) -> Self {
+        match self {
+            InputError::Parse(err) => InputError::Parse(err.with_day(day)),
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io(err) => err.fmt(f),
+            InputError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io(err) => Some(err),
+            InputError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for InputError {
+    fn from(err: std::io::Error) -> Self {
+        InputError::Io(err)
+    }
+}
+
+impl From<ParseError> for InputError {
+    fn from(err: ParseError) -> Self {
+        InputError::Parse(err)
+    }
+}
+
+/// The byte offset of `part` within `line`.  `part` must be a slice of `line`.
+pub(crate) fn offset_in(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_counts_characters() {
+        let err = ParseError::at(2, "é x", 3, "x", "expected an integer");
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn display_includes_location_and_token() {
+        let err = ParseError::at(4, "12 ab", 3, "ab", "expected an integer").with_day("01");
+        assert_eq!(
+            err.to_string(),
+            "day 01, line 4, column 4: expected an integer, found `ab`"
+        );
+    }
+
+    #[test]
+    fn display_for_missing_token() {
+        let err = ParseError::at_end(1, "12", "expected a second number");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a second number, found end of line"
+        );
+    }
+
+    #[test]
+    fn offset_of_slice() {
+        let line = "  3   45 6";
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(offset_in(line, tokens[1]), 6);
+    }
+}
@@ -0,0 +1,223 @@
+//! Named example inputs for the days, with the answers they should give.
+//!
+//! Each day has a directory in `fixtures/`, such as `fixtures/day14/`, holding the
+//! example inputs as `<name>.txt` and their answers in `expected.toml`, with a table per
+//! example:
+//!
+//! ```toml
+//! [example]
+//! part1 = 12
+//!
+//! [example.params]
+//! width = 11
+//! height = 7
+//! ```
+//!
+//! Only the parts listed are checked, since some examples only apply to one part.  The
+//! optional `params` table overrides the day's parameters for that example.  Every input
+//! needs an entry and every entry needs an input, so a misnamed file is caught rather
+//! than silently skipped.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use toml::{Table, Value};
+
+use crate::answers::display_value;
+use crate::runner::{find_day, Runnable};
+use crate::{Answer, Overrides};
+
+/// Name of the fixtures directory in the project root.
+pub const DEFAULT_FIXTURES_DIR: &str = "fixtures";
+
+/// Name of the file holding the answers in each day's directory.
+pub const EXPECTED_FILE: &str = "expected.toml";
+
+/// The fixtures directory used when none is given.
+pub fn default_fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_FIXTURES_DIR)
+}
+
+/// An example input of a day, with its expected answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+    /// The expected answer of each listed part, as it would be displayed.
+    pub expected: Vec<(usize, String)>,
+    pub overrides: Overrides,
+}
+
+impl Fixture {
+    /// A name for the fixture that is unique across all days, like `day14::example`.
+    pub fn id(&self) -> String {
+        format!("day{:02}::{}", self.day, self.name)
+    }
+
+    /// Runs the day on the input, failing unless every listed part gives the expected
+    /// answer.
+    pub fn check(&self) -> anyhow::Result<()> {
+        let day =
+            find_day(self.day).ok_or_else(|| anyhow!("Day {} is not registered", self.day))?;
+        let input = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Reading {}", self.path.display()))?;
+        let input = crate::normalize_input(&input);
+        let mut failures = Vec::new();
+        for (part, expected) in &self.expected {
+            let result = day
+                .run(&input, Some(*part), &self.overrides)
+                .with_context(|| format!("Running {}", self.id()))?;
+            let answer = &result.parts[0].answer;
+            if *answer == Answer::Unsolved || answer.to_string() != *expected {
+                failures.push(format!(
+                    "part {}: expected {}, got {}",
+                    part, expected, answer
+                ));
+            }
+        }
+        if !failures.is_empty() {
+            bail!("{} failed:\n  {}", self.id(), failures.join("\n  "));
+        }
+        Ok(())
+    }
+}
+
+/// The name of a day's fixtures directory.
+pub fn day_dir_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Loads the fixtures of a day.  A day without a directory has none.
+pub fn load_day(dir: &Path, day: &dyn Runnable) -> anyhow::Result<Vec<Fixture>> {
+    let day_dir = dir.join(day_dir_name(day.day()));
+    if !day_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let expected_path = day_dir.join(EXPECTED_FILE);
+    let text = std::fs::read_to_string(&expected_path)
+        .with_context(|| format!("Reading {}", expected_path.display()))?;
+    let table = text
+        .parse::<Table>()
+        .with_context(|| format!("Reading {}", expected_path.display()))?;
+
+    let mut inputs = Vec::new();
+    for entry in
+        std::fs::read_dir(&day_dir).with_context(|| format!("Reading {}", day_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt") {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    let mut fixtures = Vec::new();
+    for path in inputs {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| anyhow!("Bad fixture name {}", path.display()))?
+            .to_string();
+        let entry = table
+            .get(&name)
+            .ok_or_else(|| anyhow!("{} has no entry for {}", expected_path.display(), name))?;
+        let (expected, overrides) = parse_entry(entry, day)
+            .with_context(|| format!("Reading [{}] in {}", name, expected_path.display()))?;
+        fixtures.push(Fixture {
+            day: day.day(),
+            name,
+            path,
+            expected,
+            overrides,
+        });
+    }
+    if let Some(name) = table
+        .keys()
+        .find(|name| !fixtures.iter().any(|fixture| fixture.name == **name))
+    {
+        bail!(
+            "{} has answers for {} but there is no {}.txt",
+            expected_path.display(),
+            name,
+            name
+        );
+    }
+    Ok(fixtures)
+}
+
+/// Loads the fixtures of every registered day, in order of day and then name.
+pub fn load_all(dir: &Path) -> anyhow::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+    for day in crate::runner::days() {
+        fixtures.extend(load_day(dir, *day)?);
+    }
+    Ok(fixtures)
+}
+
+fn parse_entry(
+    entry: &Value,
+    day: &dyn Runnable,
+) -> anyhow::Result<(Vec<(usize, String)>, Overrides)> {
+    let entry = entry
+        .as_table()
+        .ok_or_else(|| anyhow!("Expected a table"))?;
+    let mut expected = Vec::new();
+    let mut overrides = Overrides::default();
+    for (key, value) in entry {
+        match key.as_str() {
+            "part1" => expected.push((1, display_value(value))),
+            "part2" => expected.push((2, display_value(value))),
+            "params" => {
+                let params = value
+                    .as_table()
+                    .ok_or_else(|| anyhow!("Expected params to be a table"))?;
+                for (name, value) in params {
+                    let value = value
+                        .as_integer()
+                        .ok_or_else(|| anyhow!("Expected parameter {} to be an integer", name))?;
+                    overrides.insert(name, value);
+                }
+            }
+            _ => bail!("Unexpected key {}", key),
+        }
+    }
+    if expected.is_empty() {
+        bail!("No answers given");
+    }
+    expected.sort();
+    overrides.check_declared([day.params()])?;
+    Ok((expected, overrides))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_registered_day_has_a_fixture() {
+        let dir = default_fixtures_dir();
+        for day in crate::runner::days() {
+            let fixtures = load_day(&dir, *day).unwrap();
+            assert!(!fixtures.is_empty(), "Day {} has no fixtures", day.day());
+        }
+    }
+
+    #[test]
+    fn entries_give_parts_and_params() {
+        let day = find_day(14).unwrap();
+        let entry = "part1 = 12\nparams = { width = 11, height = 7 }"
+            .parse::<Table>()
+            .unwrap();
+        let (expected, overrides) = parse_entry(&Value::Table(entry), day).unwrap();
+        assert_eq!(expected, vec![(1, "12".to_string())]);
+        assert_eq!(overrides.get("width"), Some(11));
+
+        let entry = "part1 = 12\nparams = { size = 11 }"
+            .parse::<Table>()
+            .unwrap();
+        assert!(parse_entry(&Value::Table(entry), day).is_err());
+        let entry = "part3 = 12".parse::<Table>().unwrap();
+        assert!(parse_entry(&Value::Table(entry), day).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! Random puzzle inputs, for trying the days on more than the one input each.
+//!
+//! A generator makes an input from a seed, so the same seed always gives the same input.
+//! Its sizes are parameters like the days' own, with defaults close to the size of the
+//! real inputs, and can be overridden with `--param <name>=<value>`.
+
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::day13::{ClawMachine, ClawSolution};
+use crate::{Direction4, Param, Params, Vec2};
+
+/// Makes random inputs for a day.
+pub struct Generator {
+    pub day: u8,
+    /// The sizes the generator reads from `Params`, with their defaults.
+    pub params: &'static [Param],
+    generate: fn(&mut ChaCha8Rng, &Params) -> String,
+}
+
+impl Generator {
+    /// The input for the seed.  Parameters the generator does not declare are ignored.
+    pub fn generate(&self, seed: u64, params: &Params) -> String {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        (self.generate)(&mut rng, params)
+    }
+}
+
+static GENERATORS: [Generator; 6] = [
+    Generator {
+        day: 2,
+        params: &[
+            Param {
+                name: "rows",
+                default: 1000,
+                min: 0,
+                max: 1_000_000,
+                description: "Number of reports",
+            },
+            Param {
+                name: "max_levels",
+                default: 8,
+                min: 2,
+                max: 100,
+                description: "Most levels in a report",
+            },
+        ],
+        generate: reports,
+    },
+    Generator {
+        day: 3,
+        params: &[
+            Param {
+                name: "lines",
+                default: 6,
+                min: 0,
+                max: 1000,
+                description: "Number of lines of memory",
+            },
+            Param {
+                name: "line_length",
+                default: 3000,
+                min: 0,
+                max: 1_000_000,
+                description: "Characters in each line",
+            },
+        ],
+        generate: corrupted_memory,
+    },
+    Generator {
+        day: 9,
+        params: &[Param {
+            name: "length",
+            default: 19999,
+            min: 1,
+            max: 1_000_000,
+            description: "Digits in the disk map",
+        }],
+        generate: disk_map,
+    },
+    Generator {
+        day: 10,
+        params: &[
+            Param {
+                name: "width",
+                default: 45,
+                min: 1,
+                max: 1000,
+                description: "Width of the map",
+            },
+            Param {
+                name: "height",
+                default: 45,
+                min: 1,
+                max: 1000,
+                description: "Height of the map",
+            },
+            Param {
+                name: "trails",
+                default: 150,
+                min: 0,
+                max: 100_000,
+                description: "Number of trails laid over the map",
+            },
+        ],
+        generate: height_map,
+    },
+    Generator {
+        day: 13,
+        params: &[Param {
+            name: "machines",
+            default: 320,
+            min: 0,
+            max: 100_000,
+            description: "Number of claw machines",
+        }],
+        generate: claw_machine_list,
+    },
+    Generator {
+        day: 15,
+        params: &[
+            Param {
+                name: "width",
+                default: 50,
+                min: 3,
+                max: 1000,
+                description: "Width of the warehouse",
+            },
+            Param {
+                name: "height",
+                default: 50,
+                min: 3,
+                max: 1000,
+                description: "Height of the warehouse",
+            },
+            Param {
+                name: "moves",
+                default: 20000,
+                min: 0,
+                max: 1_000_000,
+                description: "Number of moves of the robot",
+            },
+        ],
+        generate: warehouse,
+    },
+];
+
+/// All generators, in order of day.
+pub fn generators() -> &'static [Generator] {
+    &GENERATORS
+}
+
+pub fn find_generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/// The name of a generated input file, such as `09-42.txt`, which starts with the day
+/// like the real inputs do.
+pub fn file_name(day: u8, seed: u64) -> String {
+    format!("{:02}-{}.txt", day, seed)
+}
+
+// Day 2: reports that mostly rise or fall by 1 to 3, with the odd bad step.
+fn reports(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    let max_levels = params.get::<usize>("max_levels");
+    let mut input = String::new();
+    for _ in 0..params.get::<usize>("rows") {
+        let n_levels = rng.random_range(5.min(max_levels)..=max_levels);
+        let direction = if rng.random_bool(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.random_range(1..100)];
+        for _ in 1..n_levels {
+            let step = if rng.random_bool(0.05) {
+                // Too big, too small, or the wrong way.
+                *[0, 4, 5, -1, -2].choose(rng).unwrap()
+            } else {
+                rng.random_range(1..=3)
+            };
+            levels.push(levels.last().unwrap() + direction * step);
+        }
+        // Levels are positive.
+        let shift = 1 - levels.iter().min().unwrap().min(&1);
+        let line = levels
+            .iter()
+            .map(|level| (level + shift).to_string())
+            .collect::<Vec<_>>();
+        input += &line.join(" ");
+        input.push('\n');
+    }
+    input
+}
+
+// Day 3: junk with instructions, and things that nearly look like them, mixed in.
+fn corrupted_memory(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    const JUNK: &[u8] = b"mul()do'nt,[]{}<>!@#$%^&*?+-/:;_ xwhyselectfrom0123456789";
+    let mut input = String::new();
+    for _ in 0..params.get::<usize>("lines") {
+        let mut line = String::new();
+        while line.len() < params.get::<usize>("line_length") {
+            match rng.random_range(0..10) {
+                0..=2 => {
+                    line += &format!(
+                        "mul({},{})",
+                        rng.random_range(1..1000),
+                        rng.random_range(1..1000)
+                    )
+                }
+                3 => line += "do()",
+                4 => line += "don't()",
+                5 => {
+                    let near_miss = [
+                        "mul(4*",
+                        "mul(6,9!",
+                        "?(12,34)",
+                        "mul ( 2 , 4 )",
+                        "mul[3,7]",
+                        "do_not_mul(5,5)",
+                        "don't",
+                    ];
+                    line += near_miss.choose(rng).unwrap();
+                }
+                _ => {
+                    for _ in 0..rng.random_range(1..8) {
+                        line.push(*JUNK.choose(rng).unwrap() as char);
+                    }
+                }
+            }
+        }
+        // Lines don't end in spaces.
+        input += line.trim_end();
+        input.push('\n');
+    }
+    input
+}
+
+// Day 9: files of 1 to 9 blocks with 0 to 9 free blocks between them.  The length is
+// made odd so that the map ends with a file.
+fn disk_map(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    let length = params.get::<usize>("length") | 1;
+    let mut input = (0..length)
+        .map(|idx| {
+            let size = if idx % 2 == 0 {
+                rng.random_range(1..=9)
+            } else {
+                rng.random_range(0..=9)
+            };
+            char::from_digit(size, 10).unwrap()
+        })
+        .collect::<String>();
+    input.push('\n');
+    input
+}
+
+// Day 10: random heights, with trails from 0 to 9 laid over them by random walks.
+fn height_map(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    let width = params.get::<i32>("width");
+    let height = params.get::<i32>("height");
+    let mut heights = (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.random_range(0..=9))
+                .collect::<Vec<u32>>()
+        })
+        .collect::<Vec<_>>();
+    for _ in 0..params.get::<usize>("trails") {
+        let mut trail = vec![Vec2::new(
+            rng.random_range(0..width),
+            rng.random_range(0..height),
+        )];
+        while trail.len() < 10 {
+            let last = *trail.last().unwrap();
+            let next = Direction4::ALL
+                .iter()
+                .map(|direction| last + direction.unit_vector())
+                .filter(|p| (0..width).contains(&p.x) && (0..height).contains(&p.y))
+                .filter(|p| !trail.contains(p))
+                .collect::<Vec<_>>();
+            match next.choose(rng) {
+                Some(&next) => trail.push(next),
+                None => break,
+            }
+        }
+        for (step, p) in trail.iter().enumerate() {
+            heights[p.y as usize][p.x as usize] = step as u32;
+        }
+    }
+    heights
+        .iter()
+        .map(|row| {
+            let mut line = row
+                .iter()
+                .map(|&h| char::from_digit(h, 10).unwrap())
+                .collect::<String>();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+/// Claw machines whose buttons never move the claw in the same direction, each with
+/// the presses that win its prize.  Since the buttons aren't parallel, that is the only
+/// way to win.
+pub fn claw_machines(rng: &mut impl Rng, n_machines: usize) -> Vec<(ClawMachine, ClawSolution)> {
+    let mut machines = Vec::with_capacity(n_machines);
+    while machines.len() < n_machines {
+        let [a_x, a_y, b_x, b_y] = [(); 4].map(|_| rng.random_range(10..100));
+        if a_x * b_y == a_y * b_x {
+            continue;
+        }
+        let solution = ClawSolution {
+            n_a: rng.random_range(0..=100),
+            n_b: rng.random_range(0..=100),
+        };
+        let machine = ClawMachine {
+            a_x,
+            a_y,
+            b_x,
+            b_y,
+            prize_x: solution.n_a * a_x + solution.n_b * b_x,
+            prize_y: solution.n_a * a_y + solution.n_b * b_y,
+        };
+        machines.push((machine, solution));
+    }
+    machines
+}
+
+// Day 13.
+fn claw_machine_list(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    claw_machines(rng, params.get("machines"))
+        .iter()
+        .map(|(m, _)| {
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                m.a_x, m.a_y, m.b_x, m.b_y, m.prize_x, m.prize_y
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Day 15: a walled warehouse with some walls and many boxes inside, and random moves.
+fn warehouse(rng: &mut ChaCha8Rng, params: &Params) -> String {
+    const MOVES_PER_LINE: usize = 1000;
+    let width = params.get::<usize>("width");
+    let height = params.get::<usize>("height");
+    let mut cells = vec![vec!['.'; width]; height];
+    let mut floor = Vec::new();
+    for (y, row) in cells.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 || rng.random_bool(0.05) {
+                *cell = '#';
+            } else if rng.random_bool(0.25) {
+                *cell = 'O';
+            } else {
+                floor.push((x, y));
+            }
+        }
+    }
+    // The robot needs somewhere to stand.
+    let (x, y) = floor
+        .choose(rng)
+        .copied()
+        .unwrap_or((width / 2, height / 2));
+    cells[y][x] = '@';
+
+    let mut input = String::new();
+    for row in &cells {
+        input.extend(row);
+        input.push('\n');
+    }
+    input.push('\n');
+    let mut moves = ['<', '>', '^', 'v'].repeat(params.get::<usize>("moves").div_ceil(4));
+    moves.truncate(params.get("moves"));
+    moves.shuffle(rng);
+    for line in moves.chunks(MOVES_PER_LINE) {
+        input.extend(line);
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::find_day;
+    use crate::Overrides;
+
+    fn small(generator: &Generator) -> Params {
+        let mut overrides = Overrides::default();
+        for (name, value) in [
+            ("rows", 50),
+            ("lines", 2),
+            ("line_length", 300),
+            ("length", 99),
+            ("width", 12),
+            ("height", 9),
+            ("trails", 10),
+            ("machines", 20),
+            ("moves", 200),
+        ] {
+            overrides.insert(name, value);
+        }
+        Params::new(generator.params, &overrides).unwrap()
+    }
+
+    #[test]
+    fn days_solve_generated_inputs() {
+        for generator in generators() {
+            let day = find_day(generator.day).unwrap();
+            for seed in 0..5 {
+                let input = generator.generate(seed, &small(generator));
+                assert_eq!(input, crate::normalize_input(&input));
+                if let Err(err) = day.run(&input, None, &Overrides::default()) {
+                    panic!("Day {} seed {}: {:#}\n{}", generator.day, seed, err, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_input() {
+        for generator in generators() {
+            let params = small(generator);
+            assert_eq!(
+                generator.generate(7, &params),
+                generator.generate(7, &params)
+            );
+            assert_ne!(
+                generator.generate(7, &params),
+                generator.generate(8, &params)
+            );
+        }
+    }
+
+    #[test]
+    fn claw_machines_have_known_costs() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        for (machine, solution) in claw_machines(&mut rng, 100) {
+            assert_eq!(machine.cost(), Some(3 * solution.n_a + solution.n_b));
+        }
+    }
+
+    #[test]
+    fn reports_have_the_requested_size() {
+        let generator = find_generator(2).unwrap();
+        let input = generator.generate(1, &small(generator));
+        assert_eq!(input.lines().count(), 50);
+        assert!(input
+            .lines()
+            .all(|line| (2..=8).contains(&line.split(' ').count())));
+    }
+}
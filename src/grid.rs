@@ -0,0 +1,402 @@
+//! A rectangular grid of cells, stored row by row.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::{offset_in, ParseError};
+use crate::{Direction4, Direction8, Vec2};
+
+/// A cell position, with `x` the column and `y` the row counting down from the top.
+pub type Point = Vec2<usize>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// Moves the point by the offset, if that stays within a width x height grid.
+fn offset_within(point: Point, offset: Vec2<isize>, width: usize, height: usize) -> Option<Point> {
+    point
+        .checked_add_signed(offset)
+        .filter(|point| point.x < width && point.y < height)
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// A grid whose cells are given by calling `f` on each point in row-major order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vec2::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// A grid from its rows, or None if they are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a character map with one row per line, converting each character with `f`.
+    ///
+    /// Surrounding whitespace and blank lines are ignored.  It is an error for `f` to
+    /// return None, or for the rows to have different lengths.
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_with_points(input, |_, c| f(c))
+    }
+
+    /// Like `parse_with`, but also passes `f` the point of each character.
+    pub fn parse_with_points(
+        input: &str,
+        mut f: impl FnMut(Point, char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (line_idx, line) in input.lines().enumerate() {
+            let row = line.trim();
+            if row.is_empty() {
+                continue;
+            }
+            let start = offset_in(line, row);
+            let mut row_width = 0;
+            for (idx, c) in row.char_indices() {
+                if width == Some(row_width) {
+                    return Err(ParseError::at(
+                        line_idx + 1,
+                        line,
+                        start + idx,
+                        &row[idx..],
+                        format!("expected {} columns", row_width),
+                    ));
+                }
+                let cell = f(Vec2::new(row_width, height), c).ok_or_else(|| {
+                    ParseError::at(
+                        line_idx + 1,
+                        line,
+                        start + idx,
+                        &c.to_string(),
+                        "unexpected character",
+                    )
+                })?;
+                cells.push(cell);
+                row_width += 1;
+            }
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(ParseError::at(
+                        line_idx + 1,
+                        line,
+                        start + row.len(),
+                        "",
+                        format!("expected {} columns", width),
+                    ));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let width = self.width;
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * width + point.x])
+    }
+
+    /// Moves the point by the offset, or returns None if that leaves the grid.
+    pub fn offset(&self, point: Point, offset: Vec2<isize>) -> Option<Point> {
+        offset_within(point, offset, self.width, self.height)
+    }
+
+    /// The orthogonal neighbours of the point that are within the grid.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        Direction4::ALL.into_iter().filter_map(move |direction| {
+            offset_within(point, direction.unit_vector(), width, height)
+        })
+    }
+
+    /// The orthogonal and diagonal neighbours of the point that are within the grid.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        Direction8::ALL.into_iter().filter_map(move |direction| {
+            offset_within(point, direction.unit_vector(), width, height)
+        })
+    }
+
+    /// All the points in the grid, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Vec2::new(x, y)))
+    }
+
+    /// All the cells with their points, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// The first point, in row-major order, whose cell satisfies the predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "Row {} out of bounds", row);
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// The cells of the column from top to bottom.  Panics if the column is out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid reflected in its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |point| {
+            self[Vec2::new(point.y, point.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |point| {
+            self[Vec2::new(point.y, self.height - 1 - point.x)].clone()
+        })
+    }
+
+    /// The grid turned a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |point| {
+            self[Vec2::new(self.width - 1 - point.y, point.x)].clone()
+        })
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a map of ASCII characters, keeping each as its byte.
+    pub fn parse_ascii(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, |c| u8::try_from(c).ok())
+    }
+}
+
+/// Writes the rows one per line, as they would be parsed.
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "Point {:?} out of bounds for {}x{} grid",
+                point, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!(
+                "Point {:?} out of bounds for {}x{} grid",
+                point, width, height
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<u8> {
+        Grid::parse_ascii("abc\ndef").unwrap()
+    }
+
+    #[test]
+    fn displays_char_grids() {
+        let grid = example().map(|&c| char::from(c));
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parses_rows() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Vec2::new(0, 1)], b'd');
+        assert_eq!(grid.row(0), b"abc");
+    }
+
+    #[test]
+    fn parse_ignores_indentation_and_blank_lines() {
+        let grid = Grid::parse_ascii("\n    abc\n    def\n").unwrap();
+        assert_eq!(grid, example());
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let err = Grid::parse_ascii("abc\nde").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = Grid::parse_ascii("abc\ndefg").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.token, "g");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_characters() {
+        let err = Grid::parse_with("12\n3x", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.token, "x");
+    }
+
+    #[test]
+    fn checked_access() {
+        let grid = example();
+        assert_eq!(grid.get(Vec2::new(2, 0)), Some(&b'c'));
+        assert_eq!(grid.get(Vec2::new(3, 0)), None);
+        assert_eq!(grid.get(Vec2::new(0, 2)), None);
+        assert_eq!(grid.offset(Vec2::new(0, 0), Vec2::new(0, -1)), None);
+        assert_eq!(
+            grid.offset(Vec2::new(0, 0), Vec2::new(2, 1)),
+            Some(Vec2::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours4(Vec2::new(0, 0)).collect::<Vec<_>>(),
+            vec![Vec2::new(1, 0), Vec2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Vec2::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8(Vec2::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn columns() {
+        let grid = example();
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let grid = example();
+        let transposed = grid.transpose();
+        assert_eq!(transposed, Grid::parse_ascii("ad\nbe\ncf").unwrap());
+
+        let clockwise = grid.rotate_clockwise();
+        assert_eq!(clockwise, Grid::parse_ascii("da\neb\nfc").unwrap());
+        assert_eq!(clockwise.rotate_counterclockwise(), grid);
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn from_rows_rejects_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid.position(|&v| v == 3), Some(Vec2::new(0, 1)));
+    }
+}
@@ -0,0 +1,244 @@
+//! Locating the puzzle input files.
+//!
+//! The file given by `--input <file>` on the command line is always used, and it is an
+//! error if it does not exist.  Otherwise the input for a day is found by trying, in
+//! order:
+//!  1) `<day>.txt` in the directory given by `--input-dir <dir>`.
+//!  2) `<day>.txt` in the directory named by the `AOC_INPUT_DIR` environment variable.
+//!  3) `input/<day>.txt` relative to the current directory.
+//!  4) `input/<day>.txt` relative to the root of this project.
+//!
+//! The first candidate that exists is used.  If none do, the error lists every path
+//! that was tried.  Passing `--input -` reads the input from stdin instead.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable naming the directory holding the `<day>.txt` input files.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// The input path that stands for stdin.
+pub const STDIN: &str = "-";
+
+/// Name of the project-relative directory searched when nothing else is specified.
+const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Input location overrides taken from the command line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputOptions {
+    /// Use this file for the input regardless of the day.
+    pub file: Option<PathBuf>,
+    /// Look for `<day>.txt` in this directory.
+    pub dir: Option<PathBuf>,
+}
+
+impl InputOptions {
+    /// Extracts `--input <file>` and `--input-dir <dir>` (or their `--flag=value` forms)
+    /// from the arguments.  Other arguments are ignored.
+    pub fn from_args<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        let mut options = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let arg = arg.to_string_lossy().into_owned();
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(OsString::from(value))),
+                None => (arg, None),
+            };
+            let slot = match flag.as_str() {
+                "--input" => &mut options.file,
+                "--input-dir" => &mut options.dir,
+                _ => continue,
+            };
+            let value = inline_value
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{} requires a value", flag))?;
+            *slot = Some(PathBuf::from(value));
+        }
+        Ok(options)
+    }
+}
+
+static INPUT_OPTIONS: OnceLock<Result<InputOptions, String>> = OnceLock::new();
+
+/// Sets the input options used by the `read_*` helpers.
+///
+/// If this is not called, the options are parsed from the process arguments the first
+/// time an input is read.  Returns false if the options were already set.
+pub fn set_input_options(options: InputOptions) -> bool {
+    INPUT_OPTIONS.set(Ok(options)).is_ok()
+}
+
+fn input_options() -> std::io::Result<&'static InputOptions> {
+    INPUT_OPTIONS
+        .get_or_init(|| InputOptions::from_args(std::env::args_os().skip(1)))
+        .as_ref()
+        .map_err(|msg| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.clone()))
+}
+
+/// The paths that will be tried, in order, for the given day.  An explicit file is the
+/// only candidate.
+pub fn candidate_paths(day: &str, options: &InputOptions) -> Vec<PathBuf> {
+    if let Some(file) = &options.file {
+        return vec![file.clone()];
+    }
+    let file_name = Path::new(day).with_extension("txt");
+    let mut candidates = Vec::new();
+    if let Some(dir) = &options.dir {
+        candidates.push(dir.join(&file_name));
+    }
+    if let Some(dir) = std::env::var_os(INPUT_DIR_ENV) {
+        candidates.push(Path::new(&dir).join(&file_name));
+    }
+    candidates.push(Path::new(DEFAULT_INPUT_DIR).join(&file_name));
+    candidates.push(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(DEFAULT_INPUT_DIR)
+            .join(&file_name),
+    );
+    candidates
+}
+
+pub fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN)
+}
+
+/// Returns the explicit input file, `STDIN` if that was requested, or otherwise the first
+/// existing input file for the day from `candidate_paths`.
+pub fn resolve_input_path_with(day: &str, options: &InputOptions) -> std::io::Result<PathBuf> {
+    if let Some(file) = &options.file {
+        if is_stdin(file) || file.is_file() {
+            return Ok(file.clone());
+        }
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("Input file not found: {}", file.display()),
+        ));
+    }
+    let candidates = candidate_paths(day, options);
+    if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+        return Ok(path.clone());
+    }
+    let tried = candidates
+        .iter()
+        .map(|path| format!("  {}", path.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("No input found for day {}; tried:\n{}", day, tried),
+    ))
+}
+
+/// Returns the input file for the day using the process-wide input options.
+pub fn resolve_input_path(day: &str) -> std::io::Result<PathBuf> {
+    resolve_input_path_with(day, input_options()?)
+}
+
+static STDIN_CONTENTS: OnceLock<String> = OnceLock::new();
+
+// Stdin can only be read once, so keep it for any later days that also read it.
+fn stdin_contents() -> std::io::Result<&'static str> {
+    if let Some(contents) = STDIN_CONTENTS.get() {
+        return Ok(contents);
+    }
+    let mut contents = String::new();
+    std::io::stdin().lock().read_to_string(&mut contents)?;
+    Ok(STDIN_CONTENTS.get_or_init(|| contents))
+}
+
+/// Opens the input for the day, from stdin or the file found by `resolve_input_path`.
+pub fn open_input(day: &str) -> std::io::Result<Box<dyn BufRead>> {
+    open_path(&resolve_input_path(day)?)
+}
+
+/// Opens an input path that has already been resolved, which may be `STDIN`.
+pub fn open_path(path: &Path) -> std::io::Result<Box<dyn BufRead>> {
+    if is_stdin(path) {
+        Ok(Box::new(Cursor::new(stdin_contents()?.as_bytes())))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_separate_and_inline_values() {
+        let options =
+            InputOptions::from_args(["run", "--input", "a.txt", "--input-dir=some/dir", "7"])
+                .unwrap();
+        assert_eq!(
+            options,
+            InputOptions {
+                file: Some(PathBuf::from("a.txt")),
+                dir: Some(PathBuf::from("some/dir")),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_value_is_an_error() {
+        assert!(InputOptions::from_args(["--input-dir"]).is_err());
+    }
+
+    #[test]
+    fn directories_are_tried_in_order() {
+        let options = InputOptions {
+            file: None,
+            dir: Some(PathBuf::from("dir")),
+        };
+        let candidates = candidate_paths("07", &options);
+        assert_eq!(candidates[0], PathBuf::from("dir/07.txt"));
+        assert_eq!(
+            candidates.last().unwrap(),
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("input/07.txt")
+        );
+    }
+
+    #[test]
+    fn explicit_file_is_not_replaced() {
+        let options = InputOptions {
+            file: Some(PathBuf::from("/definitely/not/here/a.txt")),
+            dir: Some(PathBuf::from("dir")),
+        };
+        assert_eq!(
+            candidate_paths("07", &options),
+            vec![PathBuf::from("/definitely/not/here/a.txt")]
+        );
+        let err = resolve_input_path_with("07", &options).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert_eq!(
+            err.to_string(),
+            "Input file not found: /definitely/not/here/a.txt"
+        );
+    }
+
+    #[test]
+    fn stdin_is_not_searched_for() {
+        let options = InputOptions::from_args(["--input", "-"]).unwrap();
+        assert_eq!(
+            resolve_input_path_with("07", &options).unwrap(),
+            PathBuf::from(STDIN)
+        );
+    }
+
+    #[test]
+    fn error_lists_tried_paths() {
+        let options = InputOptions {
+            file: None,
+            dir: Some(PathBuf::from("/definitely/not/here")),
+        };
+        let err = resolve_input_path_with("99", &options).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("/definitely/not/here/99.txt"));
+    }
+}
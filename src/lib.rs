@@ -1,75 +1,195 @@
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
+use std::io::BufRead;
 
-const INPUT_BASE_PATH: &str = "/Users/alexconley/Programming/Advent Of Code/2024/input";
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod charmap;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod direction;
+pub mod error;
+pub mod fixtures;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod number_theory;
+pub mod par;
+pub mod params;
+pub mod parser;
+pub mod render;
+pub mod runner;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod vec2;
 
-fn get_input_path(day: &str) -> PathBuf {
-    let mut path = Path::new(INPUT_BASE_PATH).join(day);
-    path.set_extension("txt");
-    path
+pub use direction::{Direction4, Direction8};
+pub use error::{InputError, ParseError};
+pub use grid::Grid;
+pub use input::{resolve_input_path, set_input_options, InputOptions};
+pub use params::{Overrides, Param, Params};
+pub use solution::{Answer, Solution};
+pub use vec2::Vec2;
+
+// Each input helper comes in three forms:
+//  - parse_*(input: &str) parses input that is already in memory.
+//  - read_*_from(reader) parses anything implementing BufRead.
+//  - read_*(day) parses the day's input file, or stdin, as found by `input::open_input`.
+
+/// Cleans up the differences between how input files are saved: removes a UTF-8 byte
+/// order mark, turns CRLF line endings into LF, and strips trailing whitespace from
+/// every line and trailing blank lines from the end.  Non-empty input ends with a
+/// single newline.
+///
+/// Lines keep their numbers and columns, so parse errors still point into the file.
+pub fn normalize_input(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    let len = normalized.trim_end().len();
+    normalized.truncate(len);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Reads the whole input, cleaned up by `normalize_input`.
+pub fn read_file_as_string_from(mut reader: impl BufRead) -> std::io::Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(normalize_input(&input))
 }
 
 pub fn read_file_as_string(day: &str) -> std::io::Result<String> {
-    std::fs::read_to_string(get_input_path(day))
+    read_file_as_string_from(input::open_input(day)?)
 }
 
-pub fn read_file_as_lines(day: &str) -> std::io::Result<Vec<String>> {
-    BufReader::new(File::open(get_input_path(day))?)
+pub fn read_file_as_lines_from(reader: impl BufRead) -> std::io::Result<Vec<String>> {
+    Ok(read_file_as_string_from(reader)?
         .lines()
-        .collect()
+        .map(String::from)
+        .collect())
+}
+
+pub fn read_file_as_lines(day: &str) -> std::io::Result<Vec<String>> {
+    read_file_as_lines_from(input::open_input(day)?)
+}
+
+// Parses a token taken from `line`, reporting its position if it is not a valid `T`.
+fn parse_token<T: std::str::FromStr>(
+    line_no: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token.parse().map_err(|_| {
+        let start = error::offset_in(line, token);
+        ParseError::at(
+            line_no,
+            line,
+            start,
+            token,
+            format!("expected {}", expected),
+        )
+    })
 }
 
-pub fn read_int_pairs(day: &str) -> std::io::Result<(Vec<i32>, Vec<i32>)> {
-    let reader = BufReader::new(File::open(get_input_path(day))?);
+pub fn parse_int_pairs(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut v1 = Vec::new();
     let mut v2 = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
+    for (line_no, line) in (1..).zip(input.lines()) {
         let mut parts = line.split_whitespace();
-        v1.push(
-            parts
-                .next()
-                .expect("No first number")
-                .parse()
-                .expect("First number is not an integer"),
-        );
-        v2.push(
-            parts
-                .next()
-                .expect("No second number")
-                .parse()
-                .expect("Second number is not an integer"),
-        );
+        let first = parts
+            .next()
+            .ok_or_else(|| ParseError::at_end(line_no, line, "expected a first number"))?;
+        v1.push(parse_token(line_no, line, first, "an integer")?);
+        let second = parts
+            .next()
+            .ok_or_else(|| ParseError::at_end(line_no, line, "expected a second number"))?;
+        v2.push(parse_token(line_no, line, second, "an integer")?);
     }
     Ok((v1, v2))
 }
 
-pub fn read_numbers_with_whitespace(day: &str) -> std::io::Result<Vec<u64>> {
-    Ok(read_file_as_string(day)?
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().expect("Value is not an u64"))
-        .collect())
+pub fn read_int_pairs_from(reader: impl BufRead) -> Result<(Vec<i32>, Vec<i32>), InputError> {
+    Ok(parse_int_pairs(&read_file_as_string_from(reader)?)?)
 }
 
-pub fn read_number_grid_with_whitespace(day: &str) -> std::io::Result<Vec<Vec<i32>>> {
-    BufReader::new(File::open(get_input_path(day))?)
-        .lines()
-        .map(|line| {
-            Ok(line?
-                .split_whitespace()
-                .map(|s| s.parse::<i32>().expect("Value is not an i32"))
-                .collect::<Vec<i32>>())
+pub fn read_int_pairs(day: &str) -> Result<(Vec<i32>, Vec<i32>), InputError> {
+    read_int_pairs_from(input::open_input(day)?).map_err(|err| err.with_day(day))
+}
+
+pub fn parse_numbers_with_whitespace(input: &str) -> Result<Vec<u64>, ParseError> {
+    let mut numbers = Vec::new();
+    for (line_no, line) in (1..).zip(input.lines()) {
+        for token in line.split_whitespace() {
+            numbers.push(parse_token(line_no, line, token, "a non-negative integer")?);
+        }
+    }
+    Ok(numbers)
+}
+
+pub fn read_numbers_with_whitespace_from(reader: impl BufRead) -> Result<Vec<u64>, InputError> {
+    Ok(parse_numbers_with_whitespace(&read_file_as_string_from(
+        reader,
+    )?)?)
+}
+
+pub fn read_numbers_with_whitespace(day: &str) -> Result<Vec<u64>, InputError> {
+    read_numbers_with_whitespace_from(input::open_input(day)?).map_err(|err| err.with_day(day))
+}
+
+pub fn parse_number_grid_with_whitespace(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    (1..)
+        .zip(input.lines())
+        .map(|(line_no, line)| {
+            line.split_whitespace()
+                .map(|token| parse_token(line_no, line, token, "an integer"))
+                .collect()
         })
         .collect()
 }
 
+pub fn read_number_grid_with_whitespace_from(
+    reader: impl BufRead,
+) -> Result<Vec<Vec<i32>>, InputError> {
+    Ok(parse_number_grid_with_whitespace(
+        &read_file_as_string_from(reader)?,
+    )?)
+}
+
+pub fn read_number_grid_with_whitespace(day: &str) -> Result<Vec<Vec<i32>>, InputError> {
+    read_number_grid_with_whitespace_from(input::open_input(day)?).map_err(|err| err.with_day(day))
+}
+
+pub fn parse_ascii_grid(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.as_bytes().to_vec()).collect()
+}
+
+pub fn read_ascii_grid_from(reader: impl BufRead) -> std::io::Result<Vec<Vec<u8>>> {
+    Ok(parse_ascii_grid(&read_file_as_string_from(reader)?))
+}
+
 pub fn read_ascii_grid(day: &str) -> std::io::Result<Vec<Vec<u8>>> {
-    BufReader::new(File::open(get_input_path(day))?)
-        .lines()
-        .map(|line| Ok(line?.as_bytes().to_vec()))
-        .collect()
+    read_ascii_grid_from(input::open_input(day)?)
 }
 
 pub fn parse_to_number_grid(input: &str) -> Vec<Vec<u8>> {
@@ -85,10 +205,15 @@ pub fn parse_to_number_grid(input: &str) -> Vec<Vec<u8>> {
         .collect()
 }
 
+pub fn read_number_grid_from(reader: impl BufRead) -> std::io::Result<Vec<Vec<u8>>> {
+    Ok(parse_to_number_grid(&read_file_as_string_from(reader)?))
+}
+
 pub fn read_number_grid(day: &str) -> std::io::Result<Vec<Vec<u8>>> {
-    Ok(parse_to_number_grid(&read_file_as_string(day)?))
+    read_number_grid_from(input::open_input(day)?)
 }
 
+#[derive(Debug)]
 pub struct RulesAndUpdates {
     // Precedence of the rules.  before[x] is the set of pages that must be printed before x.
     pub before: HashMap<u16, HashSet<u16>>,
@@ -96,24 +221,148 @@ pub struct RulesAndUpdates {
     pub pages: Vec<Vec<u16>>,
 }
 
-pub fn read_rules_and_updates(day: &str) -> std::io::Result<RulesAndUpdates> {
-    let input = std::fs::read_to_string(get_input_path(day))?;
-    let (raw_rules, raw_pages) = input.split_once("\n\n").unwrap();
+pub fn parse_rules_and_updates(input: &str) -> Result<RulesAndUpdates, ParseError> {
+    use parser::{integer, lines, literal, pair, section_break, separated, Parser};
+
+    let rule = pair(integer::<u16>(), "|", integer::<u16>()).label("a rule like 47|53");
+    let update = separated(integer::<u16>(), literal(","));
+    let (rules, pages) = parser::parse_all(
+        &lines(rule).skip(section_break()).then(lines(update)),
+        input,
+    )?;
+
     let mut before = HashMap::<u16, HashSet<u16>>::new();
-    for line in raw_rules.lines() {
-        let (a, b) = line.split_once('|').expect("line did not contain |");
-        before
-            .entry(b.parse::<u16>().expect("Couldn't parse b as integer"))
-            .or_default()
-            .insert(a.parse::<u16>().expect("Couldn't parse a as integer"));
-    }
-    let pages = raw_pages
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|w| w.parse::<u16>().expect("Couldn't parse page as integer"))
-                .collect::<Vec<_>>()
-        })
-        .collect();
+    for (a, b) in rules {
+        before.entry(b).or_default().insert(a);
+    }
     Ok(RulesAndUpdates { before, pages })
 }
+
+pub fn read_rules_and_updates_from(reader: impl BufRead) -> Result<RulesAndUpdates, InputError> {
+    Ok(parse_rules_and_updates(&read_file_as_string_from(reader)?)?)
+}
+
+pub fn read_rules_and_updates(day: &str) -> Result<RulesAndUpdates, InputError> {
+    read_rules_and_updates_from(input::open_input(day)?).map_err(|err| err.with_day(day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readers_match_parsers() {
+        let input = "3   4\n4   3\n";
+        assert_eq!(
+            read_int_pairs_from(input.as_bytes()).unwrap(),
+            parse_int_pairs(input).unwrap()
+        );
+        assert_eq!(
+            read_file_as_lines_from(input.as_bytes()).unwrap(),
+            vec!["3   4", "4   3"]
+        );
+        assert_eq!(
+            read_ascii_grid_from("ab\ncd".as_bytes()).unwrap(),
+            vec![b"ab".to_vec(), b"cd".to_vec()]
+        );
+    }
+
+    #[test]
+    fn reader_reports_parse_errors() {
+        let err = read_numbers_with_whitespace_from("1 2\n3 x".as_bytes()).unwrap_err();
+        assert!(matches!(
+            err,
+            InputError::Parse(ParseError {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn normalizes_line_endings_and_whitespace() {
+        assert_eq!(
+            normalize_input("\u{feff}ab \r\ncd\t\r\n\r\n\n  \n"),
+            "ab\ncd\n"
+        );
+        assert_eq!(normalize_input("\n\nab"), "\n\nab\n");
+        assert_eq!(normalize_input(" \n\n"), "");
+        assert_eq!(
+            read_file_as_lines_from("3 4\r\n4 3 \r\n".as_bytes()).unwrap(),
+            vec!["3 4", "4 3"]
+        );
+    }
+
+    #[test]
+    fn int_pairs() {
+        assert_eq!(
+            parse_int_pairs("3   4\n4   3\n"),
+            Ok((vec![3, 4], vec![4, 3]))
+        );
+    }
+
+    #[test]
+    fn int_pairs_bad_token() {
+        let err = parse_int_pairs("3   4\n4   x3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 5, "x3"));
+    }
+
+    #[test]
+    fn int_pairs_missing_number() {
+        let err = parse_int_pairs("3   4\n4\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, ""));
+    }
+
+    #[test]
+    fn numbers_with_whitespace_bad_token() {
+        let err = parse_numbers_with_whitespace("125 17\n1 -2").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "-2"));
+    }
+
+    #[test]
+    fn number_grid_with_whitespace_bad_token() {
+        let err = parse_number_grid_with_whitespace("1 2 3\n4 5 6.5").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 5, "6.5"));
+    }
+
+    #[test]
+    fn rules_and_updates() {
+        let parsed = parse_rules_and_updates("47|53\n97|13\n\n75,47,61\n97,61").unwrap();
+        assert_eq!(parsed.before[&53], HashSet::from([47]));
+        assert_eq!(parsed.pages, vec![vec![75, 47, 61], vec![97, 61]]);
+    }
+
+    #[test]
+    fn rules_and_updates_with_crlf_and_extra_blank_lines() {
+        let input = read_file_as_string_from("47|53\r\n\r\n\r\n75,47,61\r\n\r\n".as_bytes());
+        let parsed = parse_rules_and_updates(&input.unwrap()).unwrap();
+        assert_eq!(parsed.pages, vec![vec![75, 47, 61]]);
+
+        let err = parse_rules_and_updates("47|53\n\n\n75,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (4, 4, "x"));
+    }
+
+    #[test]
+    fn rules_and_updates_missing_separator() {
+        // Without the blank line, the first update is read as a rule.
+        let err = parse_rules_and_updates("47|53\n75,47,61").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected a rule like 47|53");
+
+        let err = parse_rules_and_updates("47|53\n\n75,47\n\n61").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+    }
+
+    #[test]
+    fn rules_and_updates_bad_rule() {
+        let err = parse_rules_and_updates("47|53\n97-13\n\n75,47,61").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 1, "97-13"));
+    }
+
+    #[test]
+    fn rules_and_updates_bad_page() {
+        let err = parse_rules_and_updates("47|53\n\n75,47,61\n97,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (4, 4, "x"));
+    }
+}
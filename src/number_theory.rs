@@ -0,0 +1,368 @@
+//! Integer arithmetic that several days need: divisors, modular arithmetic, digits
+//! and small linear systems.
+
+use crate::vec2::Coordinate;
+
+/// The greatest common divisor of `|a|` and `|b|`.  `gcd(0, 0)` is zero.
+///
+/// Panics if `|a|` or `|b|` does not fit in `T`, which happens for the `MIN` of a signed
+/// type.
+pub fn gcd<T: Coordinate>(a: T, b: T) -> T {
+    checked_gcd(a, b).expect("Absolute value overflows")
+}
+
+/// The greatest common divisor of `|a|` and `|b|`, or None if either of those does not
+/// fit in `T`.
+pub fn checked_gcd<T: Coordinate>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (checked_abs(a)?, checked_abs(b)?);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    Some(a)
+}
+
+/// The least common multiple of `|a|` and `|b|`, which is zero if either is zero.
+/// Panics if it does not fit in `T`.
+pub fn lcm<T: Coordinate>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("Least common multiple overflows")
+}
+
+/// The least common multiple of `|a|` and `|b|`, or None if it does not fit in `T`.
+pub fn checked_lcm<T: Coordinate>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let (a, b) = (checked_abs(a)?, checked_abs(b)?);
+    (a / checked_gcd(a, b)?).checked_mul(b)
+}
+
+fn checked_abs<T: Coordinate>(n: T) -> Option<T> {
+    if n < T::ZERO {
+        T::ZERO.checked_sub(n)
+    } else {
+        Some(n)
+    }
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x = 1 (mod modulus)`, or None if `a` and the
+/// modulus share a factor.  The modulus must be positive.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    assert!(modulus > 0, "Modulus must be positive, got {}", modulus);
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves the congruences `x = residue (mod modulus)` with the Chinese remainder
+/// theorem, returning the smallest non-negative solution and the modulus it repeats
+/// with.
+///
+/// The moduli must be positive, but need not be coprime.  Returns None if the
+/// congruences contradict each other or the combined modulus does not fit in an i64.
+/// No congruences at all are solved by `(0, 1)`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut residue, mut modulus) = (0_i128, 1_i128);
+    for &(r, m) in congruences {
+        assert!(m > 0, "Modulus must be positive, got {}", m);
+        let (r, m) = (i128::from(r).rem_euclid(i128::from(m)), i128::from(m));
+        // Solve residue + modulus * k = r (mod m) for k.
+        let (g, inverse, _) =
+            extended_gcd(i64::try_from(modulus % m).ok()?, i64::try_from(m).ok()?);
+        let g = i128::from(g);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = ((r - residue) / g % step * i128::from(inverse)).rem_euclid(step);
+        residue += modulus * k;
+        modulus = modulus
+            .checked_mul(step)
+            .filter(|&modulus| modulus <= i128::from(i64::MAX))?;
+    }
+    Some((i64::try_from(residue).ok()?, i64::try_from(modulus).ok()?))
+}
+
+/// The number of digits of `n` written in the base, with zero having one digit.
+///
+/// Panics if `n` is negative or the base is less than two.
+pub fn digit_len<T: Coordinate>(mut n: T, base: T) -> u32 {
+    assert!(base > T::ONE, "Base must be at least two");
+    assert!(n >= T::ZERO, "Number must not be negative");
+    let mut len = 1;
+    while n >= base {
+        n = n / base;
+        len += 1;
+    }
+    len
+}
+
+/// Splits `n` so that the right part holds its last `at` digits in the base.  If `n`
+/// has no more than `at` digits, the left part is zero.
+///
+/// Panics if `n` is negative or the base is less than two.
+pub fn split_digits<T: Coordinate>(n: T, at: u32, base: T) -> (T, T) {
+    assert!(base > T::ONE, "Base must be at least two");
+    assert!(n >= T::ZERO, "Number must not be negative");
+    match checked_pow(base, at) {
+        Some(divisor) => (n / divisor, n % divisor),
+        // The divisor is bigger than any T, so all of n's digits are on the right.
+        None => (T::ZERO, n),
+    }
+}
+
+/// The number whose digits in the base are those of `left` followed by those of
+/// `right`.
+///
+/// Panics if the result does not fit in `T`, if either number is negative, or if the
+/// base is less than two.
+pub fn concat_digits<T: Coordinate>(left: T, right: T, base: T) -> T {
+    checked_concat_digits(left, right, base).expect("Concatenated number overflows")
+}
+
+/// Like `concat_digits`, but None if the result does not fit in `T`.  Still panics if
+/// either number is negative or the base is less than two.
+pub fn checked_concat_digits<T: Coordinate>(left: T, right: T, base: T) -> Option<T> {
+    assert!(left >= T::ZERO, "Number must not be negative");
+    let shift = checked_pow(base, digit_len(right, base))?;
+    left.checked_mul(shift)?.checked_add(right)
+}
+
+fn checked_pow<T: Coordinate>(base: T, exponent: u32) -> Option<T> {
+    (0..exponent).try_fold(T::ONE, |power, _| power.checked_mul(base))
+}
+
+/// Solves `matrix * [x, y] = rhs`, returning the solution only if it is unique and
+/// integral.  Returns None if the matrix is singular or the arithmetic overflows.
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> Option<[i64; 2]> {
+    let m = matrix.map(|row| row.map(i128::from));
+    let b = rhs.map(i128::from);
+    let det = det2(m[0][0], m[0][1], m[1][0], m[1][1])?;
+    let x = det2(b[0], m[0][1], b[1], m[1][1])?;
+    let y = det2(m[0][0], b[0], m[1][0], b[1])?;
+    Some([exact_quotient(x, det)?, exact_quotient(y, det)?])
+}
+
+/// Solves `matrix * [x, y, z] = rhs`, returning the solution only if it is unique and
+/// integral.  Returns None if the matrix is singular or the arithmetic overflows.
+pub fn solve_3x3(matrix: [[i64; 3]; 3], rhs: [i64; 3]) -> Option<[i64; 3]> {
+    let m = matrix.map(|row| row.map(i128::from));
+    let b = rhs.map(i128::from);
+    let det = det3(m)?;
+    let mut solution = [0; 3];
+    // Cramer's rule: replace each column in turn by the right hand side.
+    for (column, value) in solution.iter_mut().enumerate() {
+        let mut replaced = m;
+        for (row, &b) in replaced.iter_mut().zip(&b) {
+            row[column] = b;
+        }
+        *value = exact_quotient(det3(replaced)?, det)?;
+    }
+    Some(solution)
+}
+
+fn det2(a: i128, b: i128, c: i128, d: i128) -> Option<i128> {
+    a.checked_mul(d)?.checked_sub(b.checked_mul(c)?)
+}
+
+fn det3(m: [[i128; 3]; 3]) -> Option<i128> {
+    let minor = |col: usize| {
+        let (left, right) = match col {
+            0 => (1, 2),
+            1 => (0, 2),
+            _ => (0, 1),
+        };
+        det2(m[1][left], m[1][right], m[2][left], m[2][right])
+    };
+    m[0][0]
+        .checked_mul(minor(0)?)?
+        .checked_sub(m[0][1].checked_mul(minor(1)?)?)?
+        .checked_add(m[0][2].checked_mul(minor(2)?)?)
+}
+
+// The quotient as an i64, if the division is exact and the divisor is not zero.
+fn exact_quotient(numerator: i128, denominator: i128) -> Option<i64> {
+    if denominator == 0 || numerator % denominator != 0 {
+        return None;
+    }
+    i64::try_from(numerator / denominator).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0_u8, 0), 0);
+        assert_eq!(lcm(101_i16, 103), 10403);
+        assert_eq!(lcm(4, 0), 0);
+        assert_eq!(checked_lcm(-6_i8, 4), Some(12));
+        assert_eq!(checked_lcm(200_i16, 201), None);
+        assert_eq!(checked_lcm(i16::MIN, 1), None);
+        assert_eq!(checked_lcm(255_u8, 1), Some(255));
+        assert_eq!(checked_gcd(-4_i8, 6), Some(2));
+        assert_eq!(checked_gcd(i8::MIN, 2), None);
+        assert_eq!(checked_gcd(2, i64::MIN), None);
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(digit_len(0_u64, 10), 1);
+        assert_eq!(digit_len(253_000_u64, 10), 6);
+        assert_eq!(digit_len(255_u8, 2), 8);
+        assert_eq!(split_digits(253_000_u64, 3, 10), (253, 0));
+        assert_eq!(split_digits(12345_u64, 1, 10), (1234, 5));
+        assert_eq!(split_digits(12345_u64, 4, 10), (1, 2345));
+        assert_eq!(concat_digits(15_i64, 6, 10), 156);
+        assert_eq!(concat_digits(0b101_u32, 0b11, 2), 0b10111);
+    }
+
+    #[test]
+    fn digits_near_the_limits_of_the_type() {
+        assert_eq!(digit_len(i8::MAX, 10), 3);
+        // 10^5 does not fit in a u16, but every u16 has at most five digits.
+        assert_eq!(split_digits(65_535_u16, 5, 10), (0, 65_535));
+        assert_eq!(split_digits(65_535_u16, 4, 10), (6, 5535));
+        assert_eq!(checked_concat_digits(6553_u16, 5, 10), Some(65_535));
+        assert_eq!(checked_concat_digits(6553_u16, 6, 10), None);
+        assert_eq!(checked_concat_digits(1_i8, 100, 10), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Absolute value overflows")]
+    fn gcd_of_min_panics() {
+        gcd(i16::MIN, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Concatenated number overflows")]
+    fn concat_digits_panics_on_overflow() {
+        concat_digits(i64::MAX / 10, 99, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be negative")]
+    fn digits_of_negative_numbers_panic() {
+        split_digits(-12_i32, 1, 10);
+    }
+
+    #[test]
+    fn linear_systems() {
+        // Day 13's first example machine.
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some([80, 40])
+        );
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+        assert_eq!(
+            solve_3x3([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]], [8, -11, -3]),
+            Some([2, 3, -1])
+        );
+        assert_eq!(
+            solve_3x3([[1, 2, 3], [4, 5, 6], [7, 8, 9]], [1, 2, 3]),
+            None
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_and_lcm_is_multiple(a in -10_000_i64..10_000, b in -10_000_i64..10_000) {
+            let g = gcd(a, b);
+            let l = lcm(a, b);
+            if g != 0 {
+                prop_assert_eq!(a % g, 0);
+                prop_assert_eq!(b % g, 0);
+                prop_assert_eq!(g * l, (a * b).abs());
+            }
+            if l != 0 {
+                prop_assert_eq!(l % a, 0);
+                prop_assert_eq!(l % b, 0);
+            }
+        }
+
+        #[test]
+        fn extended_gcd_gives_bezout_coefficients(a in -1_000_000_i64..1_000_000, b in -1_000_000_i64..1_000_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(g, gcd(a, b));
+            prop_assert_eq!(a * x + b * y, g);
+        }
+
+        #[test]
+        fn mod_inverse_inverts(a in -1000_i64..1000, m in 1_i64..1000) {
+            match mod_inverse(a, m) {
+                Some(inverse) => {
+                    prop_assert!((0..m).contains(&inverse));
+                    prop_assert_eq!((a * inverse).rem_euclid(m), 1 % m);
+                }
+                None => prop_assert_ne!(gcd(a, m), 1),
+            }
+        }
+
+        #[test]
+        fn crt_solves_every_congruence(x in 0_i64..1_000_000, moduli in prop::collection::vec(1_i64..50, 0..5)) {
+            let congruences = moduli.iter().map(|&m| (x % m, m)).collect::<Vec<_>>();
+            let (residue, modulus) = crt(&congruences).unwrap();
+            prop_assert!((0..modulus).contains(&residue));
+            prop_assert_eq!(residue, x % modulus);
+            for &(r, m) in &congruences {
+                prop_assert_eq!(residue % m, r);
+                prop_assert_eq!(modulus % m, 0);
+            }
+        }
+
+        #[test]
+        fn split_undoes_concat(left in 0_u64..1_000_000, right in 0_u64..1_000_000, base in 2_u64..17) {
+            let joined = concat_digits(left, right, base);
+            prop_assert_eq!(split_digits(joined, digit_len(right, base), base), (left, right));
+            if left > 0 {
+                prop_assert_eq!(digit_len(joined, base), digit_len(left, base) + digit_len(right, base));
+            }
+        }
+
+        #[test]
+        fn digit_len_matches_formatting(n in any::<u64>()) {
+            prop_assert_eq!(digit_len(n, 10) as usize, n.to_string().len());
+            prop_assert_eq!(digit_len(n, 2) as usize, format!("{:b}", n).len());
+        }
+
+        #[test]
+        fn solve_2x2_recovers_solution(m in prop::array::uniform2(prop::array::uniform2(-100_i64..100)), x in prop::array::uniform2(-1000_i64..1000)) {
+            let rhs = m.map(|row| row[0] * x[0] + row[1] * x[1]);
+            let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+            prop_assert_eq!(solve_2x2(m, rhs), (det != 0).then_some(x));
+        }
+
+        #[test]
+        fn solve_3x3_recovers_solution(m in prop::array::uniform3(prop::array::uniform3(-20_i64..20)), x in prop::array::uniform3(-1000_i64..1000)) {
+            let rhs = m.map(|row| row.iter().zip(&x).map(|(a, b)| a * b).sum());
+            let det = det3(m.map(|row| row.map(i128::from))).unwrap();
+            prop_assert_eq!(solve_3x3(m, rhs), (det != 0).then_some(x));
+        }
+    }
+}
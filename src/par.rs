@@ -0,0 +1,82 @@
+//! Iterators that run in parallel when the `parallel` feature is on, and sequentially
+//! when it is off.
+//!
+//! Code that wants to share work between threads imports this prelude instead of
+//! rayon's, and calls `par_iter` or `into_par_iter` as usual.  With the feature, these
+//! are rayon's own.  Without it, they are the standard iterators, so only the methods
+//! that both kinds of iterator have should be used on them.  Closures should still be
+//! `Send` and `Sync`, so that the code builds either way.
+//!
+//! ```
+//! use rust_advent::par::prelude::*;
+//!
+//! let squares = (1..=4_u64).into_par_iter().map(|n| n * n).collect::<Vec<_>>();
+//! assert_eq!(squares, [1, 4, 9, 16]);
+//! assert_eq!([3, 1, 2].par_iter().min_by_key(|&&n| n), Some(&1));
+//! ```
+
+#[cfg(feature = "parallel")]
+pub mod prelude {
+    pub use rayon::prelude::*;
+}
+
+#[cfg(not(feature = "parallel"))]
+pub mod prelude {
+    pub use super::sequential::{IntoParallelIterator, IntoParallelRefIterator};
+}
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    /// Turns anything that can be iterated into its standard iterator.
+    pub trait IntoParallelIterator {
+        type Iter: Iterator;
+
+        fn into_par_iter(self) -> Self::Iter;
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {
+        type Iter = I::IntoIter;
+
+        fn into_par_iter(self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// Iterates over references to the items of a collection.
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, C: 'a + ?Sized> IntoParallelRefIterator<'a> for C
+    where
+        &'a C: IntoIterator,
+    {
+        type Iter = <&'a C as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+
+    #[test]
+    fn results_do_not_depend_on_threads() {
+        let numbers = (0..1000_u32).collect::<Vec<_>>();
+        let doubled = numbers.par_iter().map(|n| 2 * n).collect::<Vec<_>>();
+        assert_eq!(doubled, numbers.iter().map(|n| 2 * n).collect::<Vec<_>>());
+        assert_eq!(numbers.par_iter().sum::<u32>(), 499_500);
+
+        // Ties go to the first minimum, as they do sequentially.
+        let minimum = (0..1000_u32)
+            .into_par_iter()
+            .map(|n| (n, n % 7))
+            .min_by_key(|&(_, remainder)| remainder);
+        assert_eq!(minimum, Some((0, 0)));
+    }
+}
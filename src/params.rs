@@ -0,0 +1,253 @@
+//! Named numeric parameters of the days.
+//!
+//! A day declares its parameters, such as the size of a grid or a number of steps, with
+//! defaults for the real puzzle.  Any of them can be overridden on the command line with
+//! `--param <name>=<value>`, which makes it easy to try the smaller examples or other
+//! variants without recompiling.
+
+use std::ffi::OsString;
+
+use anyhow::{anyhow, bail, Context};
+
+/// A parameter declared by a day.
+///
+/// Overrides must be between `min` and `max` inclusive, which should fit the type the day
+/// reads the parameter as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+    pub description: &'static str,
+}
+
+impl Param {
+    pub fn contains(&self, value: i64) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// Parameter values given on the command line, which may apply to several days.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Overrides {
+    values: Vec<(String, i64)>,
+}
+
+impl Overrides {
+    /// Extracts every `--param <name>=<value>` (or `--param=<name>=<value>`) from the
+    /// arguments.  Other arguments are ignored.
+    pub fn from_args<I, S>(args: I) -> anyhow::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        let mut overrides = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            let arg = arg.to_string_lossy().into_owned();
+            let assignment = match arg.strip_prefix("--param") {
+                Some("") => args
+                    .next()
+                    .ok_or_else(|| anyhow!("--param requires a value"))?
+                    .to_string_lossy()
+                    .into_owned(),
+                Some(rest) => match rest.strip_prefix('=') {
+                    Some(assignment) => assignment.to_string(),
+                    None => continue,
+                },
+                None => continue,
+            };
+            overrides.insert_assignment(&assignment)?;
+        }
+        Ok(overrides)
+    }
+
+    /// Adds a `<name>=<value>` assignment, replacing any earlier value for the name.
+    pub fn insert_assignment(&mut self, assignment: &str) -> anyhow::Result<()> {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected <name>=<value>, got {}", assignment))?;
+        let value = value
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid value for parameter {}", name))?;
+        self.insert(name.trim(), value);
+        Ok(())
+    }
+
+    pub fn insert(&mut self, name: &str, value: i64) {
+        self.values.retain(|(n, _)| n != name);
+        self.values.push((name.to_string(), value));
+    }
+
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, value)| value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Fails if an override names a parameter that none of the declarations have, which
+    /// is almost certainly a typo.
+    pub fn check_declared<'a>(
+        &self,
+        declarations: impl IntoIterator<Item = &'a [Param]>,
+    ) -> anyhow::Result<()> {
+        let declarations = declarations.into_iter().collect::<Vec<_>>();
+        for (name, _) in &self.values {
+            if !declarations
+                .iter()
+                .any(|params| params.iter().any(|param| param.name == name))
+            {
+                bail!("Unknown parameter: {}", name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The values of one day's parameters: the overrides where given, and the defaults
+/// otherwise.
+///
+/// The default is for a day with no parameters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(&'static str, i64)>,
+}
+
+impl Params {
+    /// Fails if an override is outside the range of its parameter.  Overrides that the
+    /// day does not declare are ignored.
+    pub fn new(declared: &[Param], overrides: &Overrides) -> anyhow::Result<Self> {
+        let values = declared
+            .iter()
+            .map(|param| {
+                let value = overrides.get(param.name).unwrap_or(param.default);
+                if !param.contains(value) {
+                    bail!(
+                        "Parameter {}={} is out of range; it must be between {} and {}",
+                        param.name,
+                        value,
+                        param.min,
+                        param.max
+                    );
+                }
+                Ok((param.name, value))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { values })
+    }
+
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self::new(declared, &Overrides::default()).expect("Defaults should be in range")
+    }
+
+    /// The value of the parameter, converted to the type the day uses.
+    ///
+    /// Panics if the day did not declare the parameter, or its range does not fit the
+    /// type.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = self
+            .values
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|&(_, value)| value)
+            .unwrap_or_else(|| panic!("Parameter {} is not declared", name));
+        T::try_from(value)
+            .unwrap_or_else(|_| panic!("Parameter {}={} is out of range", name, value))
+    }
+
+    /// The names and values, in the order they were declared.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, i64)> + '_ {
+        self.values.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            min: 1,
+            max: 1000,
+            description: "Width",
+        },
+        Param {
+            name: "steps",
+            default: 100,
+            min: 0,
+            max: i64::MAX,
+            description: "Steps",
+        },
+    ];
+
+    #[test]
+    fn parses_separate_and_inline_overrides() {
+        let overrides =
+            Overrides::from_args(["run", "14", "--param", "width=11", "--param=steps=5"]).unwrap();
+        assert_eq!(overrides.get("width"), Some(11));
+        assert_eq!(overrides.get("steps"), Some(5));
+        assert_eq!(overrides.get("height"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_overrides() {
+        assert!(Overrides::from_args(["--param"]).is_err());
+        assert!(Overrides::from_args(["--param", "width"]).is_err());
+        assert!(Overrides::from_args(["--param", "width=x"]).is_err());
+    }
+
+    #[test]
+    fn overrides_replace_defaults() {
+        let overrides = Overrides::from_args(["--param", "steps=7", "--param", "other=1"]).unwrap();
+        let params = Params::new(DECLARED, &overrides).unwrap();
+        assert_eq!(params.get::<i16>("width"), 101);
+        assert_eq!(params.get::<usize>("steps"), 7);
+        assert_eq!(
+            params.iter().collect::<Vec<_>>(),
+            vec![("width", 101), ("steps", 7)]
+        );
+    }
+
+    #[test]
+    fn unknown_overrides_are_reported() {
+        let mut overrides = Overrides::default();
+        overrides.insert("steps", 3);
+        assert!(overrides.check_declared([DECLARED]).is_ok());
+        overrides.insert("stpes", 3);
+        assert!(overrides.check_declared([DECLARED]).is_err());
+    }
+
+    #[test]
+    fn values_out_of_range_are_rejected() {
+        let mut overrides = Overrides::default();
+        overrides.insert("width", 100_000);
+        let error = Params::new(DECLARED, &overrides).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parameter width=100000 is out of range; it must be between 1 and 1000"
+        );
+        overrides.insert("width", 0);
+        assert!(Params::new(DECLARED, &overrides).is_err());
+        overrides.insert("width", 1000);
+        assert!(Params::new(DECLARED, &overrides).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn ranges_that_do_not_fit_panic() {
+        let mut overrides = Overrides::default();
+        overrides.insert("width", 1000);
+        Params::new(DECLARED, &overrides)
+            .unwrap()
+            .get::<i8>("width");
+    }
+}
@@ -0,0 +1,520 @@
+//! Small parser combinators for describing an input format as a grammar.
+//!
+//! A grammar is built from integers, literals, separated lists, lines, sections and
+//! `key=value` pairs, and run over the whole input with `parse_all`:
+//!
+//! ```
+//! use rust_advent::parser::{integer, lines, pair, parse_all, Parser};
+//!
+//! let grammar = lines(pair(integer::<u32>(), "|", integer::<u32>()));
+//! assert_eq!(parse_all(&grammar, "47|53\n97|13\n").unwrap(), vec![(47, 53), (97, 13)]);
+//!
+//! let err = parse_all(&grammar, "47|53\n97-13\n").unwrap_err();
+//! assert_eq!((err.line, err.column), (2, 3));
+//! ```
+//!
+//! When the input does not match, the error points at the furthest position any part of
+//! the grammar reached, and lists everything that was expected there.
+
+use crate::ParseError;
+
+/// The input being parsed, and how far the parse has got.
+pub struct State<'t> {
+    text: &'t str,
+    offset: usize,
+    // The furthest offset where a parser failed, and what was expected there.
+    furthest: usize,
+    expected: Vec<String>,
+    // An error that ends the parse, rather than letting it try something else.
+    error: Option<ParseError>,
+}
+
+impl<'t> State<'t> {
+    fn new(text: &'t str) -> Self {
+        Self {
+            text,
+            offset: 0,
+            furthest: 0,
+            expected: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// The input that has not been parsed yet.
+    pub fn rest(&self) -> &'t str {
+        &self.text[self.offset..]
+    }
+
+    /// The byte offset of the rest of the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Consumes bytes from the start of the rest of the input.
+    pub fn advance(&mut self, n_bytes: usize) {
+        self.offset += n_bytes;
+    }
+
+    /// Records that something else was expected at the current offset, and fails.
+    pub fn expected<T>(&mut self, what: &str) -> Option<T> {
+        if self.offset > self.furthest || self.expected.is_empty() {
+            self.furthest = self.offset;
+            self.expected.clear();
+        }
+        if self.offset == self.furthest && !self.expected.iter().any(|e| e == what) {
+            self.expected.push(what.to_string());
+        }
+        None
+    }
+
+    /// Fails with an error that ends the parse, for input that matched the grammar but
+    /// is still invalid.
+    pub fn fail<T>(&mut self, offset: usize, message: impl Into<String>) -> Option<T> {
+        self.error = Some(self.error_at(offset, message));
+        None
+    }
+
+    fn error_at(&self, offset: usize, message: impl Into<String>) -> ParseError {
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |idx| offset + idx);
+        let line = &self.text[line_start..line_end];
+        let rest = &line[offset - line_start..];
+        let mut token = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+        if token.is_empty() {
+            token = rest.chars().next().map_or("", |c| &rest[..c.len_utf8()]);
+        }
+        ParseError::at(
+            self.text[..offset].matches('\n').count() + 1,
+            line,
+            offset - line_start,
+            token,
+            message,
+        )
+    }
+
+    // The error for the furthest failure.
+    fn furthest_error(&self) -> ParseError {
+        let message = if self.expected.is_empty() {
+            "unexpected input".to_string()
+        } else {
+            format!("expected {}", self.expected.join(" or "))
+        };
+        self.error_at(self.furthest, message)
+    }
+
+    // Runs the parser, going back to where it started if it fails, so that something
+    // else can be tried.
+    fn attempt<P: Parser>(&mut self, parser: &P) -> Option<P::Output> {
+        let start = self.offset;
+        let value = parser.parse(self);
+        if value.is_none() && self.error.is_none() {
+            self.offset = start;
+        }
+        value
+    }
+
+    // Whether a failure may be recovered from by trying something else.
+    fn can_backtrack(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Something that parses a value from the start of the rest of the input.
+pub trait Parser {
+    type Output;
+
+    /// Parses a value and consumes its input, or returns None having recorded what was
+    /// expected.
+    fn parse(&self, state: &mut State<'_>) -> Option<Self::Output>;
+
+    fn map<U>(self, f: impl Fn(Self::Output) -> U) -> impl Parser<Output = U>
+    where
+        Self: Sized,
+    {
+        from_fn(move |state| self.parse(state).map(&f))
+    }
+
+    /// Converts the value with a function that may reject it, which ends the parse with
+    /// an error at the start of the value.  A `ParseError` from the function, such as
+    /// from parsing the value's text, is moved to where that text is in the input.
+    fn try_map<U>(self, f: impl Fn(Self::Output) -> anyhow::Result<U>) -> impl Parser<Output = U>
+    where
+        Self: Sized,
+    {
+        from_fn(move |state| {
+            let start = state.offset;
+            let value = self.parse(state)?;
+            match f(value) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    let mut error = state.error_at(start, format!("{:#}", err));
+                    if let Some(inner) = err.downcast_ref::<ParseError>() {
+                        if inner.line == 1 {
+                            error.column += inner.column - 1;
+                        } else {
+                            error.column = inner.column;
+                        }
+                        error.line += inner.line - 1;
+                        error.token.clone_from(&inner.token);
+                        error.message.clone_from(&inner.message);
+                    }
+                    state.error = Some(error);
+                    None
+                }
+            }
+        })
+    }
+
+    /// This followed by the other parser, giving both values.
+    fn then<P: Parser>(self, other: P) -> impl Parser<Output = (Self::Output, P::Output)>
+    where
+        Self: Sized,
+    {
+        from_fn(move |state| {
+            let first = self.parse(state)?;
+            Some((first, other.parse(state)?))
+        })
+    }
+
+    /// This followed by the other parser, keeping only this value.
+    fn skip<P: Parser>(self, other: P) -> impl Parser<Output = Self::Output>
+    where
+        Self: Sized,
+    {
+        self.then(other).map(|(value, _)| value)
+    }
+
+    /// This, or the other parser if this fails.
+    fn or<P: Parser<Output = Self::Output>>(self, other: P) -> impl Parser<Output = Self::Output>
+    where
+        Self: Sized,
+    {
+        from_fn(move |state| match state.attempt(&self) {
+            Some(value) => Some(value),
+            None if state.can_backtrack() => other.parse(state),
+            None => None,
+        })
+    }
+
+    /// Reports a failure anywhere in this parser as the description being expected at
+    /// its start, which reads better for something like a whole rule.
+    fn label(self, description: &'static str) -> impl Parser<Output = Self::Output>
+    where
+        Self: Sized,
+    {
+        from_fn(move |state| {
+            let start = state.offset;
+            let saved = (state.furthest, std::mem::take(&mut state.expected));
+            let value = self.parse(state);
+            if value.is_none() && state.can_backtrack() {
+                (state.furthest, state.expected) = saved;
+                state.offset = start;
+                return state.expected(description);
+            }
+            // Keep whichever of the earlier and the new failures got further.
+            let (furthest, mut expected) = saved;
+            if furthest > state.furthest || state.expected.is_empty() {
+                (state.furthest, state.expected) = (furthest, expected);
+            } else if furthest == state.furthest {
+                expected.retain(|e| !state.expected.contains(e));
+                state.expected.extend(expected);
+            }
+            value
+        })
+    }
+}
+
+struct FromFn<F>(F);
+
+impl<T, F: Fn(&mut State<'_>) -> Option<T>> Parser for FromFn<F> {
+    type Output = T;
+
+    fn parse(&self, state: &mut State<'_>) -> Option<T> {
+        (self.0)(state)
+    }
+}
+
+/// A parser from a function, for anything the other parsers cannot express.
+pub fn from_fn<T>(f: impl Fn(&mut State<'_>) -> Option<T>) -> impl Parser<Output = T> {
+    FromFn(f)
+}
+
+/// Parses the whole input, other than any whitespace at the end.
+pub fn parse_all<P: Parser>(parser: &P, input: &str) -> Result<P::Output, ParseError> {
+    let mut state = State::new(input);
+    let value = parser.parse(&mut state);
+    if let Some(error) = state.error.take() {
+        return Err(error);
+    }
+    if let Some(value) = value {
+        let rest = state.rest().trim_start();
+        if rest.is_empty() {
+            return Ok(value);
+        }
+        state.offset = input.len() - rest.len();
+        state.expected::<()>("end of input");
+    }
+    Err(state.furthest_error())
+}
+
+/// Exactly this text.
+pub fn literal(text: &'static str) -> impl Parser<Output = ()> {
+    let description = if text == "\n" {
+        "a new line".to_string()
+    } else {
+        format!("`{}`", text)
+    };
+    from_fn(move |state| {
+        if state.rest().starts_with(text) {
+            state.advance(text.len());
+            Some(())
+        } else {
+            state.expected(&description)
+        }
+    })
+}
+
+/// A decimal integer, which may be negative.  One that does not fit in `T` ends the
+/// parse.
+pub fn integer<T: std::str::FromStr>() -> impl Parser<Output = T> {
+    from_fn(|state| {
+        let rest = state.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let n_digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if n_digits == 0 {
+            return state.expected("an integer");
+        }
+        let start = state.offset;
+        match rest[..sign + n_digits].parse() {
+            Ok(value) => {
+                state.advance(sign + n_digits);
+                Some(value)
+            }
+            Err(_) => state.fail(
+                start,
+                format!(
+                    "expected an integer that fits in {}",
+                    std::any::type_name::<T>()
+                ),
+            ),
+        }
+    })
+}
+
+/// A single character, converted by a function that returns None for characters that
+/// are not wanted.
+pub fn char_where<T>(
+    description: &'static str,
+    f: impl Fn(char) -> Option<T>,
+) -> impl Parser<Output = T> {
+    from_fn(move |state| {
+        let c = state.rest().chars().next();
+        match c.and_then(&f) {
+            Some(value) => {
+                state.advance(c.map_or(0, char::len_utf8));
+                Some(value)
+            }
+            None => state.expected(description),
+        }
+    })
+}
+
+/// Any number of items, including none, with the separator between them.
+pub fn separated<P: Parser, S: Parser>(
+    item: P,
+    separator: S,
+) -> impl Parser<Output = Vec<P::Output>> {
+    from_fn(move |state| {
+        let mut items = Vec::new();
+        match state.attempt(&item) {
+            Some(value) => items.push(value),
+            None if state.can_backtrack() => return Some(items),
+            None => return None,
+        }
+        loop {
+            match state.attempt(&(&separator).then(&item)) {
+                Some((_, value)) => items.push(value),
+                None if state.can_backtrack() => return Some(items),
+                None => return None,
+            }
+        }
+    })
+}
+
+/// One or more items, one straight after the other.
+pub fn many1<P: Parser>(item: P) -> impl Parser<Output = Vec<P::Output>> {
+    from_fn(move |state| {
+        let mut items = vec![item.parse(state)?];
+        loop {
+            match state.attempt(&item) {
+                Some(value) => items.push(value),
+                None if state.can_backtrack() => return Some(items),
+                None => return None,
+            }
+        }
+    })
+}
+
+/// One item per line, stopping at a blank line.
+pub fn lines<P: Parser>(item: P) -> impl Parser<Output = Vec<P::Output>> {
+    separated(item, line_break())
+}
+
+/// One item per section, with sections separated by blank lines.
+pub fn sections<P: Parser>(item: P) -> impl Parser<Output = Vec<P::Output>> {
+    separated(item, section_break())
+}
+
+// The end of a line that is followed by another line of the same section.
+fn line_break() -> impl Parser<Output = ()> {
+    from_fn(|state| {
+        let rest = state.rest().as_bytes();
+        if rest.first() == Some(&b'\n') && rest.get(1).is_some_and(|&b| b != b'\n') {
+            state.advance(1);
+            Some(())
+        } else {
+            state.expected("a new line")
+        }
+    })
+}
+
+/// The end of a line followed by one or more blank lines.
+pub fn section_break() -> impl Parser<Output = ()> {
+    from_fn(|state| {
+        let n_newlines = state.rest().bytes().take_while(|&b| b == b'\n').count();
+        if n_newlines >= 2 {
+            state.advance(n_newlines);
+            Some(())
+        } else {
+            state.expected("a blank line")
+        }
+    })
+}
+
+/// The rest of the section: everything up to the next blank line or the end of the
+/// input, which must not be empty.
+pub fn block() -> impl Parser<Output = String> {
+    from_fn(|state| {
+        let rest = state.rest();
+        let len = rest
+            .find("\n\n")
+            .unwrap_or(rest.trim_end_matches('\n').len());
+        if len == 0 {
+            return state.expected("a section");
+        }
+        state.advance(len);
+        Some(rest[..len].to_string())
+    })
+}
+
+/// The prefix, and then the parser's value.
+pub fn preceded<P: Parser>(prefix: &'static str, parser: P) -> impl Parser<Output = P::Output> {
+    literal(prefix).then(parser).map(|(_, value)| value)
+}
+
+/// Two values with a separator between them, like `3,4`.
+pub fn pair<A: Parser, B: Parser>(
+    first: A,
+    separator: &'static str,
+    second: B,
+) -> impl Parser<Output = (A::Output, B::Output)> {
+    first.skip(literal(separator)).then(second)
+}
+
+/// A named value like `p=3,4` or `X+94`, giving the value.
+pub fn key_value<P: Parser>(
+    key: &'static str,
+    separator: &'static str,
+    value: P,
+) -> impl Parser<Output = P::Output> {
+    preceded(key, preceded(separator, value))
+}
+
+impl<P: Parser> Parser for &P {
+    type Output = P::Output;
+
+    fn parse(&self, state: &mut State<'_>) -> Option<P::Output> {
+        (*self).parse(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(err: &ParseError) -> (usize, usize, &str) {
+        (err.line, err.column, err.token.as_str())
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_all(&integer::<i32>(), "-42\n"), Ok(-42));
+        let err = parse_all(&integer::<u8>(), "256").unwrap_err();
+        assert_eq!(position(&err), (1, 1, "256"));
+        assert_eq!(err.message, "expected an integer that fits in u8");
+        let err = parse_all(&integer::<u8>(), "x").unwrap_err();
+        assert_eq!(err.message, "expected an integer");
+    }
+
+    #[test]
+    fn separated_lists_stop_at_the_last_item() {
+        let list = separated(integer::<u32>(), literal(","));
+        assert_eq!(parse_all(&list, "1,2,3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_all(&list, ""), Ok(vec![]));
+        let err = parse_all(&list, "1,2,x").unwrap_err();
+        assert_eq!(position(&err), (1, 5, "x"));
+        assert_eq!(err.message, "expected an integer");
+    }
+
+    #[test]
+    fn lines_and_sections() {
+        let grammar = sections(lines(many1(char_where("a digit", |c| c.to_digit(10)))));
+        assert_eq!(
+            parse_all(&grammar, "12\n3\n\n\n45\n"),
+            Ok(vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5]]])
+        );
+        let err = parse_all(&grammar, "12\n3\n\n4x").unwrap_err();
+        assert_eq!(position(&err), (4, 2, "x"));
+        assert_eq!(
+            err.message,
+            "expected a digit or a new line or a blank line or end of input"
+        );
+    }
+
+    #[test]
+    fn key_values_and_alternatives() {
+        let point = || pair(integer::<i16>(), ",", integer::<i16>());
+        let grammar = key_value("p", "=", point())
+            .skip(literal(" "))
+            .then(key_value("v", "=", point()).or(key_value("v", "~", point())));
+        assert_eq!(parse_all(&grammar, "p=0,4 v~3,-3"), Ok(((0, 4), (3, -3))));
+        let err = parse_all(&grammar, "p=0,4 w=3,-3").unwrap_err();
+        assert_eq!(position(&err), (1, 7, "w=3,-3"));
+        assert_eq!(err.message, "expected `v`");
+    }
+
+    #[test]
+    fn labels_replace_inner_failures() {
+        let rule = pair(integer::<u16>(), "|", integer::<u16>()).label("a rule like 47|53");
+        let err = parse_all(&lines(rule), "47|53\n97-13").unwrap_err();
+        assert_eq!(position(&err), (2, 1, "97-13"));
+        assert_eq!(err.message, "expected a rule like 47|53 or end of input");
+    }
+
+    #[test]
+    fn errors_from_mapped_text_are_moved() {
+        let grid = block().try_map(|text| Ok(crate::Grid::parse_ascii(&text)?));
+        let grammar = integer::<u8>().skip(section_break()).then(grid);
+        let err = parse_all(&grammar, "7\n\nab\ncd\nefg").unwrap_err();
+        assert_eq!(position(&err), (5, 3, "g"));
+
+        let positive = integer::<i32>().try_map(|n| {
+            anyhow::ensure!(n > 0, "expected a positive integer");
+            Ok(n)
+        });
+        let err = parse_all(&preceded("n=", positive), "n=-3").unwrap_err();
+        assert_eq!(position(&err), (1, 3, "-3"));
+    }
+}
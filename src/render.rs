@@ -0,0 +1,307 @@
+//! Drawing grids as images, and simulations as animations.
+//!
+//! Each kind of cell gets a colour from a `Palette`, and each cell becomes a square of
+//! pixels.  Still images are written as PPM or PNG, and animations as GIF:
+//!
+//! ```
+//! use rust_advent::render::{Image, Palette};
+//! use rust_advent::Grid;
+//!
+//! let grid = Grid::parse_ascii("#.\n.#").unwrap();
+//! let palette = Palette::new([0, 0, 0], [(b'#', [255, 255, 255])]);
+//! let image = Image::from_grid(&grid, &palette, 4);
+//! assert_eq!((image.width(), image.height()), (8, 8));
+//! ```
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::Grid;
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// The colour of each kind of cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette<T> {
+    colours: Vec<(T, Rgb)>,
+    default: Rgb,
+}
+
+impl<T: PartialEq> Palette<T> {
+    /// Cells that are not listed are drawn in the default colour.
+    pub fn new(default: Rgb, colours: impl IntoIterator<Item = (T, Rgb)>) -> Self {
+        Self {
+            colours: colours.into_iter().collect(),
+            default,
+        }
+    }
+
+    /// Sets the colour of a kind of cell, replacing any earlier one.
+    pub fn with(mut self, cell: T, colour: Rgb) -> Self {
+        self.colours.retain(|(c, _)| *c != cell);
+        self.colours.push((cell, colour));
+        self
+    }
+
+    pub fn colour(&self, cell: &T) -> Rgb {
+        self.colours
+            .iter()
+            .find(|(c, _)| c == cell)
+            .map_or(self.default, |&(_, colour)| colour)
+    }
+}
+
+/// An RGB image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws each cell of the grid as a square `scale` pixels across.
+    pub fn from_grid<T: PartialEq>(grid: &Grid<T>, palette: &Palette<T>, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let (width, height) = (grid.width() * scale, grid.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in grid.rows() {
+            let line = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(palette.colour(cell), scale))
+                .collect::<Vec<_>>();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixel at column x and row y.
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Writes a binary PPM, which needs no library to read.
+    pub fn write_ppm(&self, mut writer: impl Write) -> std::io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(self.pixels.as_flattened())
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> anyhow::Result<()> {
+        let mut encoder =
+            png::Encoder::new(writer, dimension(self.width)?, dimension(self.height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(self.pixels.as_flattened())?;
+        Ok(())
+    }
+
+    /// Writes the image as PPM or PNG, depending on the extension of the path.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        match extension(path).as_deref() {
+            Some("ppm") => self.write_ppm(create(path)?)?,
+            Some("png") => self.write_png(create(path)?)?,
+            _ => bail!("Images are saved as .ppm or .png, not {}", path.display()),
+        }
+        Ok(())
+    }
+}
+
+/// Frames of the same size, shown one after the other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Animation {
+    frames: Vec<Image>,
+    /// How long each frame is shown, in hundredths of a second.
+    delay: u16,
+}
+
+impl Animation {
+    pub fn new(delay: u16) -> Self {
+        Self {
+            frames: Vec::new(),
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Writes a GIF that loops forever.  The frames may use at most 256 colours between
+    /// them, which is plenty for a palette per cell type.
+    pub fn write_gif(&self, writer: impl Write) -> anyhow::Result<()> {
+        let first = self
+            .frames
+            .first()
+            .ok_or_else(|| anyhow!("An animation needs at least one frame"))?;
+        let (width, height) = (first.width, first.height);
+        if self
+            .frames
+            .iter()
+            .any(|frame| (frame.width, frame.height) != (width, height))
+        {
+            bail!("The frames of an animation must all be the same size");
+        }
+
+        let mut colours = Vec::<Rgb>::new();
+        for frame in &self.frames {
+            for pixel in &frame.pixels {
+                if !colours.contains(pixel) {
+                    colours.push(*pixel);
+                }
+            }
+        }
+        if colours.len() > 256 {
+            bail!(
+                "A GIF can only have 256 colours, but the frames use {}",
+                colours.len()
+            );
+        }
+
+        let mut encoder = gif::Encoder::new(
+            writer,
+            dimension(width)?,
+            dimension(height)?,
+            colours.as_flattened(),
+        )?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for image in &self.frames {
+            let indices = image
+                .pixels
+                .iter()
+                .map(|pixel| colours.iter().position(|c| c == pixel).unwrap() as u8)
+                .collect::<Vec<_>>();
+            let frame = gif::Frame {
+                width: dimension(width)?,
+                height: dimension(height)?,
+                delay: self.delay,
+                buffer: Cow::Owned(indices),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if extension(path).as_deref() != Some("gif") {
+            bail!("Animations are saved as .gif, not {}", path.display());
+        }
+        self.write_gif(create(path)?)
+    }
+}
+
+fn dimension<T: TryFrom<usize>>(size: usize) -> anyhow::Result<T> {
+    T::try_from(size).map_err(|_| anyhow!("Image too large: {} pixels across", size))
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
+
+fn create(path: &Path) -> anyhow::Result<BufWriter<File>> {
+    Ok(BufWriter::new(
+        File::create(path).with_context(|| format!("Writing {}", path.display()))?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let grid = Grid::parse_ascii("#.\n.#").unwrap();
+        let palette = Palette::new([0, 0, 0], [(b'#', [255, 0, 0])]);
+        Image::from_grid(&grid, &palette, 2)
+    }
+
+    #[test]
+    fn palette_uses_default_and_latest_colour() {
+        let palette = Palette::new([1, 1, 1], [('a', [2, 2, 2])]).with('a', [3, 3, 3]);
+        assert_eq!(palette.colour(&'a'), [3, 3, 3]);
+        assert_eq!(palette.colour(&'b'), [1, 1, 1]);
+    }
+
+    #[test]
+    fn cells_are_scaled() {
+        let image = checkerboard();
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.pixel(1, 1), [255, 0, 0]);
+        assert_eq!(image.pixel(2, 1), [0, 0, 0]);
+        assert_eq!(image.pixel(3, 3), [255, 0, 0]);
+    }
+
+    #[test]
+    fn ppm_has_header_and_pixels() {
+        let mut ppm = Vec::new();
+        checkerboard().write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), b"P6\n4 4\n255\n".len() + 4 * 4 * 3);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let mut data = Vec::new();
+        checkerboard().write_png(&mut data).unwrap();
+        let mut reader = png::Decoder::new(data.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (4, 4));
+        assert_eq!(&pixels[..3], &[255, 0, 0]);
+    }
+
+    #[test]
+    fn gif_has_every_frame() {
+        let mut animation = Animation::new(5);
+        animation.push(checkerboard());
+        animation.push(checkerboard());
+        let mut data = Vec::new();
+        animation.write_gif(&mut data).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(data.as_slice()).unwrap();
+        let mut n_frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 5);
+            assert_eq!(&frame.buffer[..4], &[255, 0, 0, 255]);
+            n_frames += 1;
+        }
+        assert_eq!(n_frames, 2);
+    }
+
+    #[test]
+    fn frames_must_match() {
+        let mut animation = Animation::new(5);
+        animation.push(checkerboard());
+        let grid = Grid::parse_ascii("#").unwrap();
+        animation.push(Image::from_grid(&grid, &Palette::new([0, 0, 0], []), 1));
+        assert!(animation.write_gif(Vec::new()).is_err());
+        assert!(Animation::new(5).write_gif(Vec::new()).is_err());
+    }
+}
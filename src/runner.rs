@@ -0,0 +1,592 @@
+//! Registry of the solved days, and helpers to run them and report the answers.
+
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, Answer, Overrides, Param, Params, ParseError, Solution,
+};
+
+/// Registers a `Solution` so that it can be run alongside the other days.
+pub struct Registered<S>(PhantomData<fn() -> S>);
+
+impl<S> Registered<S> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S> Default for Registered<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type-erased access to a registered `Solution`.
+pub trait Runnable: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn params(&self) -> &'static [Param];
+
+    /// Parses the input and runs the requested part, or both of them if `part` is None.
+    /// Parameters the day does not declare are ignored.
+    fn run(
+        &self,
+        input: &str,
+        part: Option<usize>,
+        overrides: &Overrides,
+    ) -> anyhow::Result<DayResult>;
+
+    /// Parses the input and solves both parts with the day's solution and with its
+    /// reference solution, where it has one.
+    fn cross_check(&self, input: &str, overrides: &Overrides) -> anyhow::Result<Vec<CrossCheck>>;
+}
+
+impl<S: Solution> Runnable for Registered<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn run(
+        &self,
+        input: &str,
+        part: Option<usize>,
+        overrides: &Overrides,
+    ) -> anyhow::Result<DayResult> {
+        let part_numbers = match part {
+            Some(part @ 1..=2) => part..=part,
+            Some(part) => return Err(anyhow!("Day {} has no part {}", S::DAY, part)),
+            None => 1..=2,
+        };
+
+        let params = Params::new(S::PARAMS, overrides)?;
+        let start = Instant::now();
+        let parsed = S::parse(input, &params)?;
+        let parse_elapsed = start.elapsed();
+
+        let parts = part_numbers
+            .map(|part| {
+                let start = Instant::now();
+                let answer = if part == 1 {
+                    S::part1(&parsed, &params)
+                } else {
+                    S::part2(&parsed, &params)
+                };
+                PartResult {
+                    part,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+        Ok(DayResult {
+            day: S::DAY,
+            params,
+            input: None,
+            parse_elapsed,
+            parts,
+        })
+    }
+
+    fn cross_check(&self, input: &str, overrides: &Overrides) -> anyhow::Result<Vec<CrossCheck>> {
+        let params = Params::new(S::PARAMS, overrides)?;
+        let parsed = S::parse(input, &params)?;
+        Ok(vec![
+            CrossCheck {
+                day: S::DAY,
+                part: 1,
+                answer: S::part1(&parsed, &params),
+                reference: S::reference_part1(&parsed, &params),
+            },
+            CrossCheck {
+                day: S::DAY,
+                part: 2,
+                answer: S::part2(&parsed, &params),
+                reference: S::reference_part2(&parsed, &params),
+            },
+        ])
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: usize,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayResult {
+    pub day: u8,
+    /// The parameter values the day ran with.
+    pub params: Params,
+    /// Where the input came from, if it was read from a file or stdin.
+    pub input: Option<InputSource>,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// The answer to a part alongside the reference solution's answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossCheck {
+    pub day: u8,
+    pub part: usize,
+    pub answer: Answer,
+    /// None if the day has no reference solution for the part.
+    pub reference: Option<Answer>,
+}
+
+impl CrossCheck {
+    pub fn disagrees(&self) -> bool {
+        self.reference
+            .as_ref()
+            .is_some_and(|reference| *reference != self.answer)
+    }
+}
+
+/// The input a day was run on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputSource {
+    /// The file, or `-` for stdin.
+    pub path: PathBuf,
+    /// The SHA-256 of the input after `normalize_input`, in hex.
+    pub sha256: String,
+}
+
+impl InputSource {
+    pub fn new(path: PathBuf, input: &str) -> Self {
+        let sha256 = Sha256::digest(input.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        Self { path, sha256 }
+    }
+}
+
+static DAYS: [&dyn Runnable; 15] = [
+    &Registered::<day01::Day01>::new(),
+    &Registered::<day02::Day02>::new(),
+    &Registered::<day03::Day03>::new(),
+    &Registered::<day04::Day04>::new(),
+    &Registered::<day05::Day05>::new(),
+    &Registered::<day06::Day06>::new(),
+    &Registered::<day07::Day07>::new(),
+    &Registered::<day08::Day08>::new(),
+    &Registered::<day09::Day09>::new(),
+    &Registered::<day10::Day10>::new(),
+    &Registered::<day11::Day11>::new(),
+    &Registered::<day12::Day12>::new(),
+    &Registered::<day13::Day13>::new(),
+    &Registered::<day14::Day14>::new(),
+    &Registered::<day15::Day15>::new(),
+];
+
+/// All registered days, in order.
+pub fn days() -> &'static [&'static dyn Runnable] {
+    &DAYS
+}
+
+pub fn find_day(day: u8) -> Option<&'static dyn Runnable> {
+    DAYS.iter().find(|d| d.day() == day).copied()
+}
+
+/// The name of the day's input file, without extension.
+pub fn input_name(day: u8) -> String {
+    format!("{:02}", day)
+}
+
+/// Reads the day's input and runs the requested part, or all parts if `part` is None.
+pub fn run_day(
+    day: &dyn Runnable,
+    part: Option<usize>,
+    overrides: &Overrides,
+) -> anyhow::Result<DayResult> {
+    let name = input_name(day.day());
+    let path = crate::resolve_input_path(&name)?;
+    let input = crate::read_file_as_string_from(crate::input::open_path(&path)?)?;
+    let mut result = catch_panic(|| day.run(&input, part, overrides))
+        .map_err(|err| with_input_name(err, name))?;
+    result.input = Some(InputSource::new(path, &input));
+    Ok(result)
+}
+
+/// Reads the day's input and compares its answers with those of the reference solution.
+pub fn cross_check_day(
+    day: &dyn Runnable,
+    overrides: &Overrides,
+) -> anyhow::Result<Vec<CrossCheck>> {
+    let name = input_name(day.day());
+    let input = crate::read_file_as_string(&name)?;
+    catch_panic(|| day.cross_check(&input, overrides)).map_err(|err| with_input_name(err, name))
+}
+
+/// Runs the function, turning a panic into an error so that a day that panics does not
+/// stop the others from running.
+pub fn catch_panic<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        Err(anyhow!("panicked: {}", message))
+    })
+}
+
+// The days parse from a string, so only we know which input was bad.
+fn with_input_name(mut err: anyhow::Error, name: String) -> anyhow::Error {
+    if let Some(parse_err) = err.downcast_mut::<ParseError>() {
+        parse_err.day.get_or_insert(name);
+    }
+    err
+}
+
+/// How results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A table for people, from `format_table`.
+    #[default]
+    Table,
+    /// JSON for scripts, from `format_json`.
+    Json,
+}
+
+impl OutputFormat {
+    /// The format given by `--format <table|json>` (or `--format=<table|json>`) in the
+    /// arguments, defaulting to a table.  Other arguments are ignored.
+    pub fn from_args<S: AsRef<str>>(args: &[S]) -> anyhow::Result<Self> {
+        let mut format = Self::default();
+        let mut args = args.iter().map(AsRef::as_ref);
+        while let Some(arg) = args.next() {
+            let value = match arg.strip_prefix("--format") {
+                Some("") => args
+                    .next()
+                    .ok_or_else(|| anyhow!("--format requires a value"))?,
+                Some(rest) => match rest.strip_prefix('=') {
+                    Some(value) => value,
+                    None => continue,
+                },
+                None => continue,
+            };
+            format = value.parse()?;
+        }
+        Ok(format)
+    }
+
+    pub fn format(self, results: &[DayResult]) -> String {
+        match self {
+            OutputFormat::Table => format_table(results),
+            OutputFormat::Json => format_json(results),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(anyhow!("Unknown format {}; expected table or json", s)),
+        }
+    }
+}
+
+/// Formats the results as a JSON array with one record per day and part:
+///
+/// ```json
+/// {
+///   "day": 14,
+///   "part": 1,
+///   "answer": 12,
+///   "type": "uint",
+///   "params": { "height": 7, "timesteps": 100, "width": 11 },
+///   "input_path": "input/14.txt",
+///   "input_sha256": "3f4c...",
+///   "parse_ns": 41250,
+///   "elapsed_ns": 1830417
+/// }
+/// ```
+///
+/// Unsolved parts have a null answer, as do the input fields if the input was not read
+/// by `run_day`.
+pub fn format_json(results: &[DayResult]) -> String {
+    let records = results
+        .iter()
+        .flat_map(|result| {
+            let params = result
+                .params
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::from(value)))
+                .collect::<Map<_, _>>();
+            result.parts.iter().map(move |part| {
+                let answer = match &part.answer {
+                    Answer::Int(value) => Value::from(*value),
+                    Answer::UInt(value) => Value::from(*value),
+                    Answer::Text(value) => Value::from(value.as_str()),
+                    Answer::Unsolved => Value::Null,
+                };
+                json!({
+                    "day": result.day,
+                    "part": part.part,
+                    "answer": answer,
+                    "type": part.answer.type_name(),
+                    "params": params,
+                    "input_path": result.input.as_ref().map(|input| input.path.display().to_string()),
+                    "input_sha256": result.input.as_ref().map(|input| input.sha256.as_str()),
+                    "parse_ns": duration_nanos(result.parse_elapsed),
+                    "elapsed_ns": duration_nanos(part.elapsed),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut json = serde_json::to_string_pretty(&records).expect("JSON values always serialize");
+    json.push('\n');
+    json
+}
+
+fn duration_nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Formats the results as a table with one row per part.
+pub fn format_table(results: &[DayResult]) -> String {
+    let rows = results
+        .iter()
+        .flat_map(|result| {
+            result.parts.iter().map(|part| {
+                [
+                    result.day.to_string(),
+                    part.part.to_string(),
+                    part.answer.to_string(),
+                    format!("{:.2?}", part.elapsed),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    format_columns(
+        ["Day", "Part", "Answer", "Elapsed"],
+        [Align::Right, Align::Right, Align::Left, Align::Right],
+        &rows,
+    )
+}
+
+/// How the cells of a column are padded by `format_columns`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Formats the rows as a table under the header, padding each column to its widest cell.
+pub fn format_columns<const N: usize>(
+    header: [&str; N],
+    align: [Align; N],
+    rows: &[[String; N]],
+) -> String {
+    let header = header.map(String::from);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let cells = row
+            .iter()
+            .zip(widths.iter().zip(align))
+            .map(|(cell, (&width, align))| match align {
+                Align::Left => format!("{:<width$}", cell),
+                Align::Right => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Runs a single day and prints its results table.  Used by the per-day binaries, which
+/// accept `--param <name>=<value>` for the day's parameters.
+pub fn main_for_day(day: u8) -> anyhow::Result<()> {
+    let day = find_day(day).ok_or_else(|| anyhow!("Day {} is not registered", day))?;
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let overrides = Overrides::from_args(&args)?;
+    overrides.check_declared([day.params()])?;
+    let format = OutputFormat::from_args(&args)?;
+    print!("{}", format.format(&[run_day(day, None, &overrides)?]));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestDay;
+
+    impl Solution for TestDay {
+        const DAY: u8 = 99;
+        const TITLE: &'static str = "Test";
+        const PARAMS: &'static [Param] = &[Param {
+            name: "scale",
+            default: 1,
+            min: 0,
+            max: 100,
+            description: "Multiplies the part 2 answer",
+        }];
+        type Input = Vec<i32>;
+
+        fn parse(input: &str, _params: &Params) -> anyhow::Result<Self::Input> {
+            Ok(input
+                .split(',')
+                .map(|s| s.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Self::Input, _params: &Params) -> Answer {
+            input.iter().sum::<i32>().into()
+        }
+
+        fn part2(input: &Self::Input, params: &Params) -> Answer {
+            (input.iter().product::<i32>() * params.get::<i32>("scale")).into()
+        }
+
+        // Wrong for negative numbers, so that it can disagree.
+        fn reference_part1(input: &Self::Input, _params: &Params) -> Option<Answer> {
+            Some(input.iter().map(|n| n.abs()).sum::<i32>().into())
+        }
+    }
+
+    static TEST_DAY: Registered<TestDay> = Registered::new();
+
+    #[test]
+    fn days_are_registered_in_order() {
+        let numbers = days().iter().map(|d| d.day()).collect::<Vec<_>>();
+        assert_eq!(numbers, (1..=15).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn defaults_are_in_range() {
+        let generated = crate::generate::generators().iter().map(|g| g.params);
+        for param in days().iter().map(|d| d.params()).chain(generated).flatten() {
+            assert!(param.contains(param.default), "{:?}", param);
+        }
+    }
+
+    #[test]
+    fn panics_become_errors() {
+        // The message the default hook prints is captured with the test's output.
+        let err = catch_panic(|| -> anyhow::Result<()> { panic!("oops {}", 1) }).unwrap_err();
+        assert_eq!(err.to_string(), "panicked: oops 1");
+    }
+
+    #[test]
+    fn runs_all_parts() {
+        let result = TEST_DAY.run("2,3,4", None, &Overrides::default()).unwrap();
+        let answers = result
+            .parts
+            .into_iter()
+            .map(|p| p.answer)
+            .collect::<Vec<_>>();
+        assert_eq!(answers, vec![Answer::Int(9), Answer::Int(24)]);
+    }
+
+    #[test]
+    fn cross_check_reports_disagreement() {
+        let checks = TEST_DAY
+            .cross_check("2,3,4", &Overrides::default())
+            .unwrap();
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].reference, Some(Answer::Int(9)));
+        assert!(!checks[0].disagrees());
+        assert_eq!(checks[1].reference, None);
+        assert!(!checks[1].disagrees());
+
+        let checks = TEST_DAY.cross_check("2,-3", &Overrides::default()).unwrap();
+        assert!(checks[0].disagrees());
+    }
+
+    #[test]
+    fn runs_single_part() {
+        let result = TEST_DAY
+            .run("2,3,4", Some(2), &Overrides::default())
+            .unwrap();
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.parts[0].part, 2);
+        assert_eq!(result.parts[0].answer, Answer::Int(24));
+    }
+
+    #[test]
+    fn overrides_reach_the_parts() {
+        let mut overrides = Overrides::default();
+        overrides.insert("scale", 10);
+        let result = TEST_DAY.run("2,3,4", Some(2), &overrides).unwrap();
+        assert_eq!(result.parts[0].answer, Answer::Int(240));
+    }
+
+    #[test]
+    fn rejects_unknown_part() {
+        assert!(TEST_DAY
+            .run("2,3,4", Some(3), &Overrides::default())
+            .is_err());
+    }
+
+    #[test]
+    fn parse_errors_are_returned() {
+        assert!(TEST_DAY.run("2,x", None, &Overrides::default()).is_err());
+    }
+
+    #[test]
+    fn json_has_record_per_part() {
+        let mut result = TEST_DAY.run("2,3,4", None, &Overrides::default()).unwrap();
+        result.input = Some(InputSource::new(PathBuf::from("99.txt"), "2,3,4"));
+        let records: Value = serde_json::from_str(&format_json(&[result])).unwrap();
+        let records = records.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["part"], 2);
+        assert_eq!(records[1]["answer"], 24);
+        assert_eq!(records[1]["type"], "int");
+        assert_eq!(records[1]["params"]["scale"], 1);
+        assert_eq!(records[1]["input_path"], "99.txt");
+        assert_eq!(records[1]["input_sha256"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn output_format_from_args() {
+        let format = |args: &[&str]| OutputFormat::from_args(args).ok();
+        assert_eq!(format(&["run", "7"]), Some(OutputFormat::Table));
+        assert_eq!(format(&["--format", "json"]), Some(OutputFormat::Json));
+        assert_eq!(format(&["--format=table"]), Some(OutputFormat::Table));
+        assert_eq!(format(&["--format", "xml"]), None);
+    }
+
+    #[test]
+    fn table_has_header_and_row_per_part() {
+        let result = TEST_DAY.run("2,3,4", None, &Overrides::default()).unwrap();
+        let table = format_table(&[result]);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Day  Part  Answer"));
+        assert!(lines[1].starts_with(" 99     1  9 "));
+    }
+}
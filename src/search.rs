@@ -0,0 +1,341 @@
+//! Graph searches over states whose neighbours are given by a function.
+//!
+//! States are usually grid points, but anything `Copy + Eq + Hash` will do, so a state
+//! can also carry a direction or other bookkeeping.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Every state reachable from the starts, including the starts themselves.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashSet<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut stack = starts.into_iter().collect::<Vec<_>>();
+    let mut seen = stack.iter().copied().collect::<HashSet<_>>();
+    while let Some(state) = stack.pop() {
+        for next in neighbours(&state) {
+            if seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+/// The states reachable from the start in depth-first order.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut order = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(state) = stack.pop() {
+        if !seen.insert(state) {
+            continue;
+        }
+        order.push(state);
+        // Push in reverse so that the first neighbour is explored first.
+        let mut next = neighbours(&state).into_iter().collect::<Vec<_>>();
+        next.reverse();
+        stack.extend(next.into_iter().filter(|n| !seen.contains(n)));
+    }
+    order
+}
+
+/// The number of steps from the start to every reachable state.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state] + 1;
+        for next in neighbours(&state) {
+            distances.entry(next).or_insert_with(|| {
+                queue.push_back(next);
+                distance
+            });
+        }
+    }
+    distances
+}
+
+/// A path with the fewest steps from the start to a goal, including both ends.
+pub fn bfs_path<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(reconstruct_path(&parents, state));
+        }
+        for next in neighbours(&state) {
+            if seen.insert(next) {
+                parents.insert(next, state);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// The number of distinct paths from the start that end at a goal.  Paths stop at the
+/// first goal they reach.
+///
+/// The graph must be acyclic, or this will not terminate.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        state: N,
+        neighbours: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        counts: &mut HashMap<N, u64>,
+    ) -> u64
+    where
+        N: Copy + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        if is_goal(&state) {
+            return 1;
+        }
+        if let Some(&n) = counts.get(&state) {
+            return n;
+        }
+        let next = neighbours(&state).into_iter().collect::<Vec<_>>();
+        let n = next
+            .into_iter()
+            .map(|next| count(next, neighbours, is_goal, counts))
+            .sum();
+        counts.insert(state, n);
+        n
+    }
+
+    count(start, &mut neighbours, &mut is_goal, &mut HashMap::new())
+}
+
+/// The cheapest cost from the start to every reachable state.  `neighbours` gives each
+/// next state with the cost of moving to it, which must not be negative.
+pub fn dijkstra<N, C, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start, C::default())]);
+    let mut heap = BinaryHeap::from([Reverse((C::default(), start))]);
+    while let Some(Reverse((cost, state))) = heap.pop() {
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next, next_cost);
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    costs
+}
+
+/// The cheapest path from the start to a goal and its cost, found with A*.
+///
+/// `heuristic` estimates the remaining cost to a goal and must never overestimate it.
+/// A heuristic that always returns zero makes this Dijkstra's algorithm.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start, C::default())]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+    while let Some(Reverse((_, cost, state))) = heap.pop() {
+        if costs.get(&state).is_some_and(|&best| best < cost) {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, reconstruct_path(&parents, state)));
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next, next_cost);
+                parents.insert(next, state);
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest path from the start to a goal and its cost.
+pub fn dijkstra_path<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Copy + Eq + Hash + Ord,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+// Follows the parents back from the end, which has no parent only if it is the start.
+fn reconstruct_path<N: Copy + Eq + Hash>(parents: &HashMap<N, N>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(&parent) = parents.get(path.last().unwrap()) {
+        path.push(parent);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Vec2};
+
+    // Open cells of the map, with walls marked by `#`.
+    fn open_neighbours(grid: &Grid<u8>) -> impl FnMut(&Vec2<usize>) -> Vec<Vec2<usize>> + '_ {
+        |&point| {
+            grid.neighbours4(point)
+                .filter(|&next| grid[next] != b'#')
+                .collect()
+        }
+    }
+
+    const MAZE: &str = "
+        ..#.
+        .##.
+        ....";
+
+    #[test]
+    fn reachable_stops_at_walls() {
+        let grid = Grid::parse_ascii("..#.\n###.\n....").unwrap();
+        let region = reachable([Vec2::new(0, 0)], open_neighbours(&grid));
+        assert_eq!(region, HashSet::from([Vec2::new(0, 0), Vec2::new(1, 0)]));
+    }
+
+    #[test]
+    fn dfs_visits_each_state_once() {
+        let grid = Grid::parse_ascii(MAZE).unwrap();
+        let order = dfs(Vec2::new(0, 0), open_neighbours(&grid));
+        assert_eq!(order.len(), 9);
+        assert_eq!(order[0], Vec2::new(0, 0));
+        assert_eq!(order.iter().collect::<HashSet<_>>().len(), 9);
+    }
+
+    #[test]
+    fn bfs_distances() {
+        let grid = Grid::parse_ascii(MAZE).unwrap();
+        let distances = bfs(Vec2::new(0, 0), open_neighbours(&grid));
+        assert_eq!(distances[&Vec2::new(3, 0)], 7);
+        assert_eq!(distances[&Vec2::new(1, 0)], 1);
+        assert!(!distances.contains_key(&Vec2::new(2, 0)));
+    }
+
+    #[test]
+    fn bfs_path_is_shortest() {
+        let grid = Grid::parse_ascii(MAZE).unwrap();
+        let goal = Vec2::new(3, 0);
+        let path = bfs_path(Vec2::new(0, 0), open_neighbours(&grid), |&p| p == goal).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path.first(), Some(&Vec2::new(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(bfs_path(Vec2::new(0, 0), open_neighbours(&grid), |&p| p
+            == Vec2::new(2, 0))
+        .is_none());
+    }
+
+    #[test]
+    fn counts_paths_through_a_dag() {
+        // Moving only right or down through a 3x3 grid.
+        let grid = Grid::new(3, 3, b'.');
+        let paths = count_paths(
+            Vec2::new(0, 0),
+            |&p: &Vec2<usize>| {
+                [Vec2::new(p.x + 1, p.y), Vec2::new(p.x, p.y + 1)]
+                    .into_iter()
+                    .filter(|&next| grid.contains(next))
+            },
+            |&p| p == Vec2::new(2, 2),
+        );
+        assert_eq!(paths, 6);
+    }
+
+    // Each cell costs its digit to enter.
+    fn weighted_neighbours(
+        grid: &Grid<u8>,
+    ) -> impl FnMut(&Vec2<usize>) -> Vec<(Vec2<usize>, u32)> + '_ {
+        |&point| {
+            grid.neighbours4(point)
+                .map(|next| (next, u32::from(grid[next])))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        let grid =
+            Grid::parse_with("1163\n1381\n2136", |c| c.to_digit(10).map(|d| d as u8)).unwrap();
+        let goal = Vec2::new(3, 2);
+
+        let costs = dijkstra(Vec2::new(0, 0), weighted_neighbours(&grid));
+        assert_eq!(costs[&goal], 13);
+
+        let (cost, path) =
+            dijkstra_path(Vec2::new(0, 0), weighted_neighbours(&grid), |&p| p == goal).unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(
+            path.iter()
+                .skip(1)
+                .map(|&p| u32::from(grid[p]))
+                .sum::<u32>(),
+            13
+        );
+
+        let (cost, _) = astar(
+            Vec2::new(0, 0),
+            weighted_neighbours(&grid),
+            |&p| p.manhattan(goal) as u32,
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 13);
+    }
+}
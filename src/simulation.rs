@@ -0,0 +1,374 @@
+//! Step-by-step simulations, and a terminal player for watching them.
+//!
+//! Days that move things around a map, like the guard of day 6 or the robots of days 14
+//! and 15, implement `Simulation` so that their intermediate states can be inspected.
+//! The `Player` shows one state at a time using nothing but ANSI escapes, and reads a
+//! command per line from stdin:
+//!
+//!  - Enter or `s` pauses and takes a single step.
+//!  - `p` plays or pauses.
+//!  - `g <n>`, or just `<n>`, pauses and jumps to step n, even backwards.
+//!  - `+` and `-` double and halve the speed.
+//!  - `q` quits.
+
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+
+/// A state that advances one step at a time.
+pub trait Simulation {
+    /// Takes a step, or returns false without changing anything once the simulation
+    /// has finished.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state as lines of text.
+    fn render(&self) -> String;
+}
+
+/// Steps per second that a player starts at.
+pub const DEFAULT_SPEED: f64 = 10.0;
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 4096.0;
+// Faster than this, several steps are taken between frames.
+const MAX_FRAMES_PER_SECOND: f64 = 50.0;
+
+// Clears the screen and moves the cursor to its top left corner.
+const CLEAR: &str = "\x1b[H\x1b[2J";
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Something to do to the player, parsed from a line of input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Step,
+    TogglePlay,
+    Jump(usize),
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let line = line.trim();
+        let command = match line {
+            "" | "s" => Self::Step,
+            "p" => Self::TogglePlay,
+            "+" => Self::Faster,
+            "-" => Self::Slower,
+            "q" => Self::Quit,
+            _ => {
+                let step = line.strip_prefix('g').unwrap_or(line).trim();
+                if step.is_empty() || !step.bytes().all(|b| b.is_ascii_digit()) {
+                    bail!("Unknown command: {}", line);
+                }
+                Self::Jump(step.parse().context("Invalid step")?)
+            }
+        };
+        Ok(command)
+    }
+}
+
+/// Plays a simulation back, keeping its first state so that it can jump backwards.
+pub struct Player<S> {
+    initial: S,
+    current: S,
+    step: usize,
+    finished: bool,
+    playing: bool,
+    /// In steps per second.
+    speed: f64,
+    message: Option<String>,
+}
+
+impl<S: Simulation + Clone> Player<S> {
+    /// A paused player at the first step.
+    pub fn new(simulation: S) -> Self {
+        Self {
+            current: simulation.clone(),
+            initial: simulation,
+            step: 0,
+            finished: false,
+            playing: false,
+            speed: DEFAULT_SPEED,
+            message: None,
+        }
+    }
+
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self
+    }
+
+    /// The number of steps taken so far.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.current
+    }
+
+    /// Carries out the command, returning false if it was to quit.
+    pub fn apply(&mut self, command: Command) -> bool {
+        self.message = None;
+        match command {
+            Command::Step => {
+                self.playing = false;
+                self.advance(1);
+            }
+            Command::TogglePlay => {
+                self.playing = !self.playing && !self.finished;
+            }
+            Command::Jump(step) => {
+                self.playing = false;
+                self.jump_to(step);
+            }
+            Command::Faster => self.speed = (self.speed * 2.0).min(MAX_SPEED),
+            Command::Slower => self.speed = (self.speed / 2.0).max(MIN_SPEED),
+            Command::Quit => return false,
+        }
+        true
+    }
+
+    /// Goes to the given step, starting again from the first if it is behind us.  Stops
+    /// early if the simulation finishes first.
+    pub fn jump_to(&mut self, step: usize) {
+        if step < self.step {
+            self.current = self.initial.clone();
+            self.step = 0;
+            self.finished = false;
+        }
+        self.advance(step - self.step);
+        if self.step < step {
+            self.message = Some(format!("Finished before step {}", step));
+        }
+    }
+
+    // Takes up to n steps, pausing if the simulation finishes.
+    fn advance(&mut self, n: usize) {
+        for _ in 0..n {
+            if !self.current.step() {
+                self.finished = true;
+                self.playing = false;
+                break;
+            }
+            self.step += 1;
+        }
+    }
+
+    /// The screen showing the current state: the rendered simulation and a status line.
+    pub fn frame(&self) -> String {
+        let state = if self.finished {
+            "finished"
+        } else if self.playing {
+            "playing"
+        } else {
+            "paused"
+        };
+        let mut frame = format!(
+            "{}{}\n{}step {}{}  {}  {} steps/s\n",
+            CLEAR,
+            self.current.render().trim_end(),
+            BOLD,
+            self.step,
+            RESET,
+            state,
+            self.speed
+        );
+        if let Some(message) = &self.message {
+            frame.push_str(message);
+            frame.push('\n');
+        }
+        frame.push_str("[Enter] step  [p] play/pause  [g <n>] jump  [+/-] speed  [q] quit\n> ");
+        frame
+    }
+
+    // How long to wait between frames while playing, and how many steps each takes.
+    fn tick(&self) -> (Duration, usize) {
+        let frames_per_second = self.speed.min(MAX_FRAMES_PER_SECOND);
+        let steps = (self.speed / frames_per_second).round() as usize;
+        (
+            Duration::from_secs_f64(1.0 / frames_per_second),
+            steps.max(1),
+        )
+    }
+
+    /// Shows the simulation on the output, taking commands from the receiver until one
+    /// says to quit or the sender hangs up.
+    pub fn run(&mut self, commands: &Receiver<String>, mut out: impl Write) -> anyhow::Result<()> {
+        loop {
+            out.write_all(self.frame().as_bytes())?;
+            out.flush()?;
+            let line = if self.playing {
+                let (delay, steps) = self.tick();
+                match commands.recv_timeout(delay) {
+                    Ok(line) => line,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.advance(steps);
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            } else {
+                match commands.recv() {
+                    Ok(line) => line,
+                    Err(_) => return Ok(()),
+                }
+            };
+            match line.parse::<Command>() {
+                Ok(command) => {
+                    if !self.apply(command) {
+                        return Ok(());
+                    }
+                }
+                Err(err) => self.message = Some(err.to_string()),
+            }
+        }
+    }
+}
+
+/// Plays the simulation in the terminal, reading commands from stdin, until the user
+/// quits or stdin is closed.
+pub fn play<S: Simulation + Clone>(simulation: S, speed: f64) -> anyhow::Result<()> {
+    let (sender, commands) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "{}", ENTER_ALTERNATE_SCREEN)?;
+    let result = Player::new(simulation)
+        .with_speed(speed)
+        .run(&commands, &mut stdout);
+    write!(stdout, "{}", LEAVE_ALTERNATE_SCREEN)?;
+    stdout.flush()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up to a limit.
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+            self.value += 1;
+            true
+        }
+
+        fn render(&self) -> String {
+            format!("{}\n", self.value)
+        }
+    }
+
+    fn player(limit: usize) -> Player<Counter> {
+        Player::new(Counter { value: 0, limit })
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!("".parse::<Command>().unwrap(), Command::Step);
+        assert_eq!(" p ".parse::<Command>().unwrap(), Command::TogglePlay);
+        assert_eq!("g 12".parse::<Command>().unwrap(), Command::Jump(12));
+        assert_eq!("7".parse::<Command>().unwrap(), Command::Jump(7));
+        assert_eq!("q".parse::<Command>().unwrap(), Command::Quit);
+        assert!("g".parse::<Command>().is_err());
+        assert!("g -1".parse::<Command>().is_err());
+        assert!("x".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn steps_and_jumps_both_ways() {
+        let mut player = player(10);
+        player.apply(Command::Step);
+        assert_eq!((player.step(), player.simulation().value), (1, 1));
+        player.apply(Command::Jump(7));
+        assert_eq!((player.step(), player.simulation().value), (7, 7));
+        player.apply(Command::Jump(3));
+        assert_eq!((player.step(), player.simulation().value), (3, 3));
+    }
+
+    #[test]
+    fn stops_when_finished() {
+        let mut player = player(4);
+        player.apply(Command::TogglePlay);
+        assert!(player.is_playing());
+        player.apply(Command::Jump(9));
+        assert_eq!(player.step(), 4);
+        assert!(player.is_finished() && !player.is_playing());
+        assert!(player.frame().contains("Finished before step 9"));
+        player.apply(Command::TogglePlay);
+        assert!(!player.is_playing());
+        player.apply(Command::Jump(0));
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn speed_is_bounded() {
+        let mut player = player(1).with_speed(MAX_SPEED);
+        player.apply(Command::Faster);
+        assert_eq!(player.speed(), MAX_SPEED);
+        assert_eq!(
+            player.tick().1,
+            (MAX_SPEED / MAX_FRAMES_PER_SECOND).round() as usize
+        );
+        for _ in 0..20 {
+            player.apply(Command::Slower);
+        }
+        assert_eq!(player.speed(), MIN_SPEED);
+        assert_eq!(player.tick(), (Duration::from_secs(4), 1));
+    }
+
+    #[test]
+    fn runs_commands_until_quit() {
+        let (sender, commands) = mpsc::channel();
+        for line in ["", "", "x", "g 5", "q", "s"] {
+            sender.send(line.to_string()).unwrap();
+        }
+        let mut player = player(10);
+        let mut out = Vec::new();
+        player.run(&commands, &mut out).unwrap();
+        assert_eq!(player.step(), 5);
+
+        let out = String::from_utf8(out).unwrap();
+        let frames = out.split(CLEAR).skip(1).collect::<Vec<_>>();
+        assert_eq!(frames.len(), 5);
+        assert!(frames[0].starts_with("0\n"));
+        assert!(frames[3].contains("Unknown command: x"));
+        assert!(frames[4].starts_with("5\n"));
+    }
+}
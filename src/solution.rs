@@ -0,0 +1,116 @@
+//! The common shape of every day's solution.
+
+use std::fmt;
+
+use crate::params::{Param, Params};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    /// The part has not been solved yet.
+    Unsolved,
+}
+
+impl Answer {
+    /// The variant, as used in machine-readable output.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::Int(_) => "int",
+            Answer::UInt(_) => "uint",
+            Answer::Text(_) => "text",
+            Answer::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::UInt(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as _)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int: i8, i16, i32, i64, isize);
+answer_from!(UInt: u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A solution to a single day.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// The day number, starting from 1.
+    const DAY: u8;
+    /// The title of the puzzle.
+    const TITLE: &'static str;
+
+    /// The parameters the day reads from `Params`, with their defaults.
+    const PARAMS: &'static [Param] = &[];
+
+    /// The parsed form of the puzzle input.
+    type Input;
+
+    fn parse(input: &str, params: &Params) -> anyhow::Result<Self::Input>;
+    fn part1(input: &Self::Input, params: &Params) -> Answer;
+    fn part2(input: &Self::Input, params: &Params) -> Answer;
+
+    /// A slow but straightforward solution of part 1, which `part1` is checked against.
+    /// None if the day has no reference solution, or it would take too long with these
+    /// parameters.
+    fn reference_part1(_input: &Self::Input, _params: &Params) -> Option<Answer> {
+        None
+    }
+
+    /// Like `reference_part1`, for part 2.
+    fn reference_part2(_input: &Self::Input, _params: &Params) -> Option<Answer> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_keep_their_sign() {
+        assert_eq!(Answer::from(-3_i32), Answer::Int(-3));
+        assert_eq!(Answer::from(7_usize), Answer::UInt(7));
+    }
+
+    #[test]
+    fn display_matches_value() {
+        assert_eq!(Answer::from(123_u64).to_string(), "123");
+        assert_eq!(Answer::from(-12_i64).to_string(), "-12");
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+}
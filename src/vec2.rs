@@ -0,0 +1,289 @@
+//! A two-dimensional point or displacement with integer coordinates.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::number_theory::gcd;
+
+/// The integer types that can be used as coordinates.
+pub trait Coordinate:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `|self - other|`, without overflowing for unsigned types.
+    fn abs_diff(self, other: Self) -> Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs_diff(self, other: Self) -> Self {
+                    if self > other {
+                        self - other
+                    } else {
+                        other - self
+                    }
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A point or displacement.  On grids, `x` is the column and `y` the row, with y
+/// increasing downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vec2<U> {
+        Vec2::new(f(self.x), f(self.y))
+    }
+
+    /// Converts to a wider coordinate type.
+    pub fn cast<U: From<T>>(self) -> Vec2<U> {
+        self.map(U::from)
+    }
+
+    /// Converts to another coordinate type, or returns None if either coordinate does
+    /// not fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec2<U>> {
+        Some(Vec2::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+}
+
+impl<T: Coordinate> Vec2<T> {
+    pub const ZERO: Self = Self::new(T::ZERO, T::ZERO);
+
+    /// The distance moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance allowing diagonal moves.
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The shortest displacement in the same direction with integer coordinates, found
+    /// by dividing both by their greatest common divisor.  The zero vector is unchanged.
+    pub fn reduced(self) -> Self {
+        let divisor = gcd(self.x, self.y);
+        if divisor == T::ZERO {
+            self
+        } else {
+            Self::new(self.x / divisor, self.y / divisor)
+        }
+    }
+}
+
+impl Vec2<usize> {
+    /// Moves by a signed displacement, or returns None if that goes below zero.
+    pub fn checked_add_signed(self, offset: Vec2<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+// Componentwise operations between vectors.
+macro_rules! vector_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T: $op<Output = T>> $op for Vec2<T> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                Self::new(self.x.$method(other.x), self.y.$method(other.y))
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>> $op_assign for Vec2<T> {
+            fn $method_assign(&mut self, other: Self) {
+                *self = (*self).$method(other);
+            }
+        }
+    };
+}
+
+vector_op!(Add, add, AddAssign, add_assign);
+vector_op!(Sub, sub, SubAssign, sub_assign);
+
+// Operations applying a scalar to both coordinates.
+macro_rules! scalar_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl<T: Copy + $op<Output = T>> $op<T> for Vec2<T> {
+            type Output = Self;
+
+            fn $method(self, scalar: T) -> Self {
+                Self::new(self.x.$method(scalar), self.y.$method(scalar))
+            }
+        }
+
+        impl<T: Copy + $op<Output = T>> $op_assign<T> for Vec2<T> {
+            fn $method_assign(&mut self, scalar: T) {
+                *self = self.$method(scalar);
+            }
+        }
+    };
+}
+
+scalar_op!(Mul, mul, MulAssign, mul_assign);
+scalar_op!(Div, div, DivAssign, div_assign);
+scalar_op!(Rem, rem, RemAssign, rem_assign);
+
+// Scalar multiplication with the scalar on the left.
+macro_rules! left_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<Vec2<$t>> for $t {
+                type Output = Vec2<$t>;
+
+                fn mul(self, v: Vec2<$t>) -> Vec2<$t> {
+                    v * self
+                }
+            }
+        )*
+    };
+}
+
+left_scalar_mul!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Coordinate> Sum for Vec2<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(1, -2);
+        let b = Vec2::new(4, 3);
+        assert_eq!(a + b, Vec2::new(5, 1));
+        assert_eq!(a - b, Vec2::new(-3, -5));
+        assert_eq!(-a, Vec2::new(-1, 2));
+        assert_eq!(a * 3, Vec2::new(3, -6));
+        assert_eq!(3 * a, a * 3);
+        assert_eq!(b / 2, Vec2::new(2, 1));
+        assert_eq!(b % 2, Vec2::new(0, 1));
+        assert_eq!([a, b, a].into_iter().sum::<Vec2<i32>>(), Vec2::new(6, -1));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 2;
+        assert_eq!(c, Vec2::new(8, 6));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vec2::new(1_u8, 7);
+        let b = Vec2::new(4, 2);
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(a.chebyshev(b), 5);
+        assert_eq!(Vec2::new(-3, 2).manhattan(Vec2::ZERO), 5);
+    }
+
+    #[test]
+    fn reduction_keeps_direction() {
+        assert_eq!(Vec2::new(6, -4).reduced(), Vec2::new(3, -2));
+        assert_eq!(Vec2::new(0, -5).reduced(), Vec2::new(0, -1));
+        assert_eq!(Vec2::new(3_u32, 5).reduced(), Vec2::new(3, 5));
+        assert_eq!(Vec2::<i64>::ZERO.reduced(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn conversions() {
+        let v = Vec2::new(200_u8, 3);
+        assert_eq!(v.cast::<i32>(), Vec2::new(200, 3));
+        assert_eq!(v.try_cast::<i8>(), None);
+        assert_eq!(Vec2::new(-1_i64, 2).try_cast::<usize>(), None);
+        assert_eq!(
+            Vec2::new(1_i64, 2).try_cast::<usize>(),
+            Some(Vec2::new(1, 2))
+        );
+        assert_eq!(Vec2::from((1, 2)), Vec2::new(1, 2));
+    }
+
+    #[test]
+    fn signed_offsets() {
+        let v = Vec2::new(1_usize, 0);
+        assert_eq!(
+            v.checked_add_signed(Vec2::new(-1, 2)),
+            Some(Vec2::new(0, 2))
+        );
+        assert_eq!(v.checked_add_signed(Vec2::new(0, -1)), None);
+    }
+}
@@ -0,0 +1,24 @@
+//! Runs every example in `fixtures/` as its own test, so that adding an example only
+//! takes an input file and its answers.  See `rust_advent::fixtures` for the layout.
+
+use libtest_mimic::{Arguments, Failed, Trial};
+use rust_advent::fixtures::{default_fixtures_dir, load_all};
+
+fn main() {
+    let args = Arguments::from_args();
+    let fixtures = load_all(&default_fixtures_dir()).unwrap_or_else(|err| {
+        eprintln!("Error loading fixtures: {:#}", err);
+        std::process::exit(101);
+    });
+    let trials = fixtures
+        .into_iter()
+        .map(|fixture| {
+            Trial::test(fixture.id(), move || {
+                fixture
+                    .check()
+                    .map_err(|err| Failed::from(format!("{:#}", err)))
+            })
+        })
+        .collect();
+    libtest_mimic::run(&args, trials).exit();
+}
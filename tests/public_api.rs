@@ -0,0 +1,55 @@
+//! Uses the days through the library's public API, as code outside the crate would.
+
+use rust_advent::day09::{Disk, DiskList};
+use rust_advent::day12::find_regions;
+use rust_advent::day13::{ClawMachine, ClawSolution};
+use rust_advent::day14::Day14;
+use rust_advent::{Overrides, Params, Solution};
+
+#[test]
+fn disk_types_defrag() {
+    let mut disk = Disk::new_from_string("2333133121414131402");
+    disk.defrag();
+    assert_eq!(disk.checksum(), 1928);
+
+    let mut disk = DiskList::new_from_string("2333133121414131402");
+    disk.defrag();
+    assert_eq!(disk.checksum(), 2858);
+}
+
+#[test]
+fn regions_are_inspectable() {
+    let grid =
+        rust_advent::day12::Day12::parse("AAAA\nBBCD\nBBCC\nEEEC\n", &Params::default()).unwrap();
+    let regions = find_regions(&grid);
+    assert_eq!(regions.len(), 5);
+    assert_eq!(
+        regions.iter().map(|region| region.area()).sum::<usize>(),
+        16
+    );
+    assert_eq!(regions.iter().map(|region| region.cost()).sum::<u32>(), 140);
+}
+
+#[test]
+fn claw_machines_can_be_built_directly() {
+    let machine = ClawMachine {
+        a_x: 94,
+        a_y: 34,
+        b_x: 22,
+        b_y: 67,
+        prize_x: 8400,
+        prize_y: 5400,
+    };
+    assert_eq!(machine.solve(), Some(ClawSolution { n_a: 80, n_b: 40 }));
+    assert_eq!(machine.cost(), Some(280));
+}
+
+#[test]
+fn parameters_are_passed_to_parse() {
+    let mut overrides = Overrides::default();
+    overrides.insert("width", 11);
+    overrides.insert("height", 7);
+    let params = Params::new(Day14::PARAMS, &overrides).unwrap();
+    let robots = Day14::parse("p=2,4 v=2,-3\n", &params).unwrap();
+    assert_eq!((robots.width(), robots.height()), (11, 7));
+}